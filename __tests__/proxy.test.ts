@@ -96,6 +96,18 @@ describe("proxy", () => {
     },
   );
 
+  it("bypasses AuthKit for desktop crash reports", async () => {
+    const { default: proxy } = await import("../proxy");
+
+    const response = await proxy(
+      createRequest({ pathname: "/api/desktop/crash-reports" }),
+    );
+
+    expect(response).toMatchObject({ kind: "next" });
+    expect(mockAuthkit).not.toHaveBeenCalled();
+    expect(mockNextResponseRedirect).not.toHaveBeenCalled();
+  });
+
   it.each(["/robots.txt", "/sitemap.xml"])(
     "bypasses AuthKit for the public SEO route %s",
     async (pathname) => {
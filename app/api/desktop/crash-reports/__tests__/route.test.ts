@@ -0,0 +1,144 @@
+import { beforeEach, describe, expect, it, jest } from "@jest/globals";
+import type { NextRequest } from "next/server";
+
+jest.mock("next/server", () => ({
+  NextResponse: class MockNextResponse {
+    status: number;
+    headers: Headers;
+    private body: unknown;
+
+    constructor(body?: unknown, init?: ResponseInit) {
+      this.body = body;
+      this.status = init?.status ?? 200;
+      this.headers = new Headers(init?.headers);
+    }
+
+    static json(body: unknown, init?: ResponseInit) {
+      return new MockNextResponse(body, init);
+    }
+
+    async json() {
+      return this.body;
+    }
+  },
+}));
+
+const mockEvent = jest.fn();
+const mockWarn = jest.fn();
+const mockFlush = jest.fn(async () => {});
+const mockLimit = jest.fn(async () => ({ success: true }));
+let mockRedis: object | null = null;
+
+jest.mock("@/lib/posthog/server", () => ({
+  phLogger: {
+    event: mockEvent,
+    warn: mockWarn,
+    flush: mockFlush,
+  },
+}));
+
+jest.mock("@/lib/rate-limit/redis", () => ({
+  createRedisClient: () => mockRedis,
+}));
+
+jest.mock("@upstash/ratelimit", () => {
+  const Ratelimit = jest.fn(() => ({ limit: mockLimit }));
+  Object.assign(Ratelimit, { slidingWindow: jest.fn() });
+  return { Ratelimit };
+});
+
+const report = {
+  id: "1700000000000-0123456789abcdef0123456789abcdef",
+  kind: "panic",
+  createdAt: 1_700_000_000_000,
+  appVersion: "0.1.0",
+  os: "macos",
+  arch: "aarch64",
+  thread: "main",
+  message: "called `Option::unwrap()` on a `None` value",
+  location: "src/lib.rs:1:1",
+  backtrace: null,
+};
+
+function createRequest(
+  body: string,
+  userAgent = "HackerAI-Desktop/0.1.0",
+): NextRequest {
+  return {
+    headers: new Headers({
+      "user-agent": userAgent,
+      "x-forwarded-for": "203.0.113.7, 10.0.0.1",
+    }),
+    text: async () => body,
+  } as unknown as NextRequest;
+}
+
+async function post(request: NextRequest) {
+  const { POST } = await import("../route");
+  return POST(request);
+}
+
+describe("POST /api/desktop/crash-reports", () => {
+  beforeEach(() => {
+    jest.resetModules();
+    jest.clearAllMocks();
+    mockRedis = null;
+    mockLimit.mockResolvedValue({ success: true });
+  });
+
+  it("records a valid report", async () => {
+    const response = await post(createRequest(JSON.stringify(report)));
+
+    expect(response.status).toBe(202);
+    expect(mockEvent).toHaveBeenCalledWith(
+      "desktop_crash_report",
+      expect.objectContaining({
+        report_id: report.id,
+        kind: "panic",
+        app_version: "0.1.0",
+        message: report.message,
+      }),
+    );
+    expect(mockFlush).toHaveBeenCalled();
+  });
+
+  it("rejects requests that don't come from the desktop app", async () => {
+    const response = await post(
+      createRequest(JSON.stringify(report), "Mozilla/5.0"),
+    );
+
+    expect(response.status).toBe(403);
+    expect(mockEvent).not.toHaveBeenCalled();
+  });
+
+  it("rejects malformed and invalid reports", async () => {
+    expect((await post(createRequest("{"))).status).toBe(400);
+    expect(
+      (await post(createRequest(JSON.stringify({ ...report, id: "../x" }))))
+        .status,
+    ).toBe(400);
+    expect(mockEvent).not.toHaveBeenCalled();
+  });
+
+  it("rejects oversized reports", async () => {
+    const response = await post(
+      createRequest(
+        JSON.stringify({ ...report, backtrace: "x".repeat(300 * 1024) }),
+      ),
+    );
+
+    expect(response.status).toBe(413);
+    expect(mockEvent).not.toHaveBeenCalled();
+  });
+
+  it("rate limits by client IP when Redis is configured", async () => {
+    mockRedis = {};
+    mockLimit.mockResolvedValue({ success: false });
+
+    const response = await post(createRequest(JSON.stringify(report)));
+
+    expect(response.status).toBe(429);
+    expect(mockLimit).toHaveBeenCalledWith("203.0.113.7");
+    expect(mockEvent).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,120 @@
+import { Ratelimit } from "@upstash/ratelimit";
+import { NextResponse, type NextRequest } from "next/server";
+import { z } from "zod";
+import { phLogger } from "@/lib/posthog/server";
+import { createRedisClient } from "@/lib/rate-limit/redis";
+
+export const dynamic = "force-dynamic";
+export const maxDuration = 10;
+export const runtime = "nodejs";
+
+// Crash reports are sent by the desktop app's native HTTP client, which has
+// no web session, so this route is reachable without authentication. It
+// only accepts reports the user chose to send (see upload_crash_report in
+// packages/desktop), and limits their size and rate per IP.
+const MAX_BODY_BYTES = 256 * 1024;
+const REPORTS_PER_HOUR = 10;
+const DESKTOP_USER_AGENT_PREFIX = "HackerAI-Desktop/";
+
+const crashReportSchema = z.object({
+  id: z.string().regex(/^[A-Za-z0-9-]{1,128}$/),
+  kind: z.enum(["panic", "unclean-exit"]),
+  createdAt: z.number().int().nonnegative(),
+  appVersion: z.string().max(64),
+  os: z.string().max(32),
+  arch: z.string().max(32),
+  thread: z.string().max(256).nullish(),
+  message: z.string().max(4_000),
+  location: z.string().max(1_000).nullish(),
+  backtrace: z.string().max(128_000).nullish(),
+});
+
+let rateLimiter: Ratelimit | null | undefined;
+
+function getRateLimiter(): Ratelimit | null {
+  if (rateLimiter === undefined) {
+    const redis = createRedisClient();
+    rateLimiter = redis
+      ? new Ratelimit({
+          redis,
+          limiter: Ratelimit.slidingWindow(REPORTS_PER_HOUR, "1 h"),
+          prefix: "desktop:crash-reports",
+        })
+      : null;
+  }
+  return rateLimiter;
+}
+
+function clientIp(request: NextRequest): string {
+  return (
+    request.headers.get("x-forwarded-for")?.split(",")[0]?.trim() ||
+    request.headers.get("x-real-ip") ||
+    "unknown"
+  );
+}
+
+function json(body: Record<string, unknown>, status: number) {
+  return NextResponse.json(body, {
+    status,
+    headers: { "Cache-Control": "no-store" },
+  });
+}
+
+export async function POST(request: NextRequest) {
+  const userAgent = request.headers.get("user-agent") ?? "";
+  if (!userAgent.startsWith(DESKTOP_USER_AGENT_PREFIX)) {
+    return json(
+      { error: "Crash reports are only accepted from the app" },
+      403,
+    );
+  }
+
+  const limiter = getRateLimiter();
+  if (limiter) {
+    const { success } = await limiter.limit(clientIp(request));
+    if (!success) {
+      return json({ error: "Too many crash reports" }, 429);
+    }
+  }
+
+  const body = await request.text();
+  if (Buffer.byteLength(body) > MAX_BODY_BYTES) {
+    return json({ error: "Crash report is too large" }, 413);
+  }
+
+  let payload: unknown;
+  try {
+    payload = JSON.parse(body);
+  } catch {
+    return json({ error: "Crash report must be JSON" }, 400);
+  }
+  const parsed = crashReportSchema.safeParse(payload);
+  if (!parsed.success) {
+    return json({ error: "Invalid crash report" }, 400);
+  }
+
+  const report = parsed.data;
+  phLogger.event("desktop_crash_report", {
+    report_id: report.id,
+    kind: report.kind,
+    crashed_at: new Date(report.createdAt).toISOString(),
+    app_version: report.appVersion,
+    os: report.os,
+    arch: report.arch,
+    thread: report.thread ?? null,
+    message: report.message,
+    location: report.location ?? null,
+    backtrace: report.backtrace ?? null,
+    user_agent: userAgent,
+  });
+  phLogger.warn("Desktop crash report received", {
+    event: "desktop_crash_report_received",
+    report_id: report.id,
+    kind: report.kind,
+    app_version: report.appVersion,
+    os: report.os,
+  });
+  await phLogger.flush();
+
+  return json({ received: true }, 202);
+}
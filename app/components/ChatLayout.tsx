@@ -9,6 +9,7 @@ import { SidebarProvider } from "@/components/ui/sidebar";
 import Loading from "@/components/ui/loading";
 import MainSidebar from "./Sidebar";
 import { onOpenSettingsDialog } from "@/lib/utils/settings-dialog";
+import { useDesktopEvents } from "../hooks/useDesktopEvents";
 
 const SettingsDialog = dynamic(
   () => import("./SettingsDialog").then((module) => module.SettingsDialog),
@@ -63,6 +64,8 @@ export function ChatLayout({ children }: { children: React.ReactNode }) {
     [handleOpenSettings],
   );
 
+  useDesktopEvents();
+
   // Escape key handler and focus trap for mobile overlay
   useEffect(() => {
     if (!isMobile || !chatSidebarOpen) return;
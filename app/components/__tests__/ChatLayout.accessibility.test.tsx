@@ -23,6 +23,9 @@ jest.mock("@/app/hooks/useChats", () => ({
     loadMore: jest.fn(),
   }),
 }));
+jest.mock("@/app/hooks/useDesktopEvents", () => ({
+  useDesktopEvents: () => undefined,
+}));
 jest.mock("@/components/ui/sidebar", () => ({
   SidebarProvider: ({ children }: { children: ReactNode }) => (
     <div>{children}</div>
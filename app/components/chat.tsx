@@ -58,6 +58,8 @@ import {
   LEGACY_AGENT_RESUME_ENDPOINT,
 } from "@/lib/api/agent-endpoints";
 import { isTauriEnvironment } from "@/app/hooks/useTauri";
+import { useMiniStatus } from "@/app/hooks/useMiniStatus";
+import { useAgentNotifications } from "@/app/hooks/useAgentNotifications";
 import {
   stripAgentLongHeartbeatParts,
   stripAgentLongHeartbeatPartsFromMessages,
@@ -999,6 +1001,20 @@ export const Chat = ({ autoResume }: { autoResume: boolean }) => {
     temporaryChatsEnabledRef,
   ]);
 
+  useMiniStatus({
+    chatTitle,
+    messages: messages as ChatMessage[],
+    status,
+    todos,
+  });
+  useAgentNotifications({
+    chatId: temporaryChatsEnabled ? null : chatId,
+    chatTitle,
+    messages: messages as ChatMessage[],
+    status,
+    hasStoredApprovalRequest: storedAgentApprovalRequest !== null,
+  });
+
   // Keep refs in sync so closures read latest values
   setMessagesRef.current = setMessages;
   messagesRef.current = messages;
@@ -0,0 +1,54 @@
+import { agentEventFor, hasPendingApproval } from "../useAgentNotifications";
+import type { ChatMessage } from "@/types/chat";
+
+function message(role: "user" | "assistant", parts: unknown[]): ChatMessage {
+  return { id: `${role}-1`, role, parts } as ChatMessage;
+}
+
+describe("agentEventFor", () => {
+  it("reports a finished run", () => {
+    expect(agentEventFor("streaming", "ready", false)).toBe("completed");
+    expect(agentEventFor("submitted", "ready", false)).toBe("completed");
+  });
+
+  it("reports a failed run", () => {
+    expect(agentEventFor("streaming", "error", false)).toBe("failed");
+  });
+
+  it("prefers a new approval request over the run finishing", () => {
+    expect(agentEventFor("streaming", "ready", true)).toBe(
+      "approval_required",
+    );
+  });
+
+  it("ignores transitions that aren't the end of a run", () => {
+    expect(agentEventFor(null, "ready", false)).toBeNull();
+    expect(agentEventFor("ready", "submitted", false)).toBeNull();
+    expect(agentEventFor("submitted", "streaming", false)).toBeNull();
+  });
+});
+
+describe("hasPendingApproval", () => {
+  it("finds a tool waiting for approval in the latest reply", () => {
+    expect(
+      hasPendingApproval([
+        message("assistant", [
+          { type: "text", text: "Running nmap" },
+          { type: "tool-run_terminal_cmd", state: "approval-requested" },
+        ]),
+      ]),
+    ).toBe(true);
+  });
+
+  it("ignores resolved tools and user messages", () => {
+    expect(
+      hasPendingApproval([
+        message("assistant", [
+          { type: "tool-run_terminal_cmd", state: "output-available" },
+        ]),
+      ]),
+    ).toBe(false);
+    expect(hasPendingApproval([message("user", [])])).toBe(false);
+    expect(hasPendingApproval([])).toBe(false);
+  });
+});
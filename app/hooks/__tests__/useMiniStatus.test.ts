@@ -0,0 +1,90 @@
+import { buildMiniStatus } from "../useMiniStatus";
+import type { ChatMessage, Todo } from "@/types/chat";
+
+function message(id: string, role: "user" | "assistant", text: string) {
+  return {
+    id,
+    role,
+    parts: [{ type: "text", text }],
+  } as ChatMessage;
+}
+
+function todo(id: string, status: Todo["status"]): Todo {
+  return { id, content: id, status };
+}
+
+describe("buildMiniStatus", () => {
+  const messages = [
+    message("user-1", "user", "Scan example.com"),
+    message("assistant-1", "assistant", "Found  two\nopen ports."),
+  ];
+
+  it("reports idle for an empty chat", () => {
+    expect(
+      buildMiniStatus({
+        chatTitle: null,
+        messages: [],
+        status: "ready",
+        todos: [],
+      }),
+    ).toEqual({
+      status: null,
+      chatTitle: null,
+      lastMessage: null,
+      progress: null,
+    });
+  });
+
+  it("shows the pending request while running", () => {
+    expect(
+      buildMiniStatus({
+        chatTitle: "Recon",
+        messages: [...messages, message("user-2", "user", "Now port 8080")],
+        status: "streaming",
+        todos: [],
+      }),
+    ).toMatchObject({ status: "running", lastMessage: "Now port 8080" });
+  });
+
+  it("shows the latest reply once done", () => {
+    expect(
+      buildMiniStatus({
+        chatTitle: "Recon",
+        messages,
+        status: "ready",
+        todos: [],
+      }),
+    ).toMatchObject({
+      status: "done",
+      chatTitle: "Recon",
+      lastMessage: "Found two open ports.",
+    });
+  });
+
+  it("derives progress from completed todos", () => {
+    expect(
+      buildMiniStatus({
+        chatTitle: null,
+        messages,
+        status: "submitted",
+        todos: [
+          todo("a", "completed"),
+          todo("b", "in_progress"),
+          todo("c", "pending"),
+          todo("d", "completed"),
+        ],
+      }).progress,
+    ).toBe(0.5);
+  });
+
+  it("truncates long messages", () => {
+    const { lastMessage } = buildMiniStatus({
+      chatTitle: null,
+      messages: [message("assistant-1", "assistant", "x".repeat(500))],
+      status: "ready",
+      todos: [],
+    });
+    expect(lastMessage).toHaveLength(200);
+    expect(lastMessage?.endsWith("…")).toBe(true);
+  });
+});
@@ -0,0 +1,112 @@
+"use client";
+
+import { useEffect, useRef } from "react";
+import type { UseChatHelpers } from "@ai-sdk/react";
+import type { ChatMessage } from "@/types/chat";
+import {
+  isTauriEnvironment,
+  notifyAgentEvent,
+  setBadgeCount,
+  type AgentEventKind,
+} from "@/app/hooks/useTauri";
+
+/** Notifications shown since the window was last focused, for the badge. */
+let unseenEvents = 0;
+
+async function notify(
+  kind: AgentEventKind,
+  chatTitle: string | null,
+  chatId: string | null,
+) {
+  if (await notifyAgentEvent(kind, chatTitle, chatId)) {
+    unseenEvents += 1;
+    await setBadgeCount(unseenEvents);
+  }
+}
+
+function clearUnseenEvents() {
+  if (unseenEvents === 0) return;
+  unseenEvents = 0;
+  void setBadgeCount(0);
+}
+
+export interface UseAgentNotificationsParams {
+  /** `null` when the chat can't be reopened, e.g. a temporary chat. */
+  chatId: string | null;
+  chatTitle: string | null;
+  messages: ChatMessage[];
+  status: UseChatHelpers<ChatMessage>["status"];
+  /** Approval pending outside the message parts (background agent runs). */
+  hasStoredApprovalRequest: boolean;
+}
+
+export function hasPendingApproval(messages: ChatMessage[]): boolean {
+  const last = messages[messages.length - 1];
+  if (!last || last.role !== "assistant") return false;
+  return last.parts.some(
+    (part) => "state" in part && part.state === "approval-requested",
+  );
+}
+
+/**
+ * The agent event a status change or new approval request should notify
+ * about, if any.
+ */
+export function agentEventFor(
+  previousStatus: UseChatHelpers<ChatMessage>["status"] | null,
+  status: UseChatHelpers<ChatMessage>["status"],
+  approvalRequested: boolean,
+): AgentEventKind | null {
+  if (approvalRequested) return "approval_required";
+  const wasRunning =
+    previousStatus === "submitted" || previousStatus === "streaming";
+  if (!wasRunning) return null;
+  if (status === "ready") return "completed";
+  if (status === "error") return "failed";
+  return null;
+}
+
+/**
+ * Raise native desktop notifications when an agent run finishes, fails, or
+ * starts waiting for approval, and count them on the dock / taskbar badge
+ * until the window is focused again. No-op outside the desktop app.
+ */
+export function useAgentNotifications({
+  chatId,
+  chatTitle,
+  messages,
+  status,
+  hasStoredApprovalRequest,
+}: UseAgentNotificationsParams) {
+  const previousStatusRef = useRef<typeof status | null>(null);
+  const previousApprovalRef = useRef(false);
+  const approvalPending =
+    hasStoredApprovalRequest || hasPendingApproval(messages);
+
+  useEffect(() => {
+    if (!isTauriEnvironment()) return;
+    window.addEventListener("focus", clearUnseenEvents);
+    return () => window.removeEventListener("focus", clearUnseenEvents);
+  }, []);
+
+  useEffect(() => {
+    previousStatusRef.current = null;
+    previousApprovalRef.current = false;
+  }, [chatId]);
+
+  useEffect(() => {
+    const previousStatus = previousStatusRef.current;
+    const approvalRequested = approvalPending && !previousApprovalRef.current;
+    previousStatusRef.current = status;
+    previousApprovalRef.current = approvalPending;
+    if (!isTauriEnvironment()) return;
+
+    const kind = agentEventFor(previousStatus, status, approvalRequested);
+    if (kind) {
+      void notify(kind, chatTitle, chatId);
+    }
+    // Only status and approval transitions notify; the title and id are read
+    // as they are at that moment
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [status, approvalPending]);
+}
@@ -0,0 +1,135 @@
+"use client";
+
+import { useEffect } from "react";
+import { useRouter } from "next/navigation";
+import { useGlobalState } from "@/app/contexts/GlobalState";
+import { useStartNewChat } from "@/app/hooks/useStartNewChat";
+import {
+  isTauriEnvironment,
+  takePendingDeepLink,
+  type DeepLinkRoute,
+} from "@/app/hooks/useTauri";
+import { useLatestRef } from "@/app/hooks/useLatestRef";
+import { upsertDraft } from "@/lib/utils/client-storage";
+import { openSettingsDialog } from "@/lib/utils/settings-dialog";
+
+type DesktopEventHandlers = Record<string, (payload: unknown) => void>;
+
+export function focusChatInput() {
+  const textarea = document.querySelector<HTMLTextAreaElement>(
+    'textarea[data-testid="chat-input"]',
+  );
+  if (!textarea) return;
+  textarea.focus();
+  const end = textarea.value.length;
+  textarea.setSelectionRange(end, end);
+}
+
+/**
+ * Handle events the desktop shell sends to this window. Listeners are
+ * registered once; handlers always see the latest render's values.
+ * No-op outside the desktop app.
+ */
+export function useDesktopEvents() {
+  const router = useRouter();
+  const { setInput } = useGlobalState();
+  const startNewChat = useStartNewChat();
+
+  const openDeepLink = (route: DeepLinkRoute) => {
+    switch (route.view) {
+      case "chat":
+        router.push(`/c/${route.chatId}`);
+        break;
+      case "new-chat":
+        startNewChat();
+        // Prefill only: links come from untrusted places, so the user
+        // reviews and sends the prompt themselves. The draft keeps it when
+        // the new chat's input mounts and restores its draft.
+        if (route.prompt) {
+          upsertDraft("new", route.prompt);
+          setInput(route.prompt);
+        }
+        break;
+      case "settings":
+        openSettingsDialog(route.section ?? undefined);
+        break;
+    }
+  };
+  const openDeepLinkRef = useLatestRef(openDeepLink);
+
+  const handlersRef = useLatestRef<DesktopEventHandlers>({
+    // Global shortcut brought the window forward
+    "focus-chat-input": () => focusChatInput(),
+    // Native menu items the web app implements
+    "menu-action": (payload) => {
+      const { action } = payload as { action: string };
+      if (action === "new-chat") startNewChat();
+    },
+    // Window focused after an agent notification for another chat
+    "notification-open-chat": (payload) => {
+      const { chatId } = payload as { chatId: string };
+      router.push(`/c/${chatId}`);
+    },
+    // The route is also held by the desktop app until taken; taking it here
+    // keeps a reload from opening it again
+    "deep-link-navigate": () => {
+      void takePendingDeepLink().then(
+        (route) => route && openDeepLinkRef.current(route),
+      );
+    },
+  });
+
+  // A link that launched the app arrives before the main window can listen
+  useEffect(() => {
+    if (!isTauriEnvironment()) return;
+
+    let cancelled = false;
+    (async () => {
+      const { getCurrentWebviewWindow } = await import(
+        "@tauri-apps/api/webviewWindow"
+      );
+      if (getCurrentWebviewWindow().label !== "main") return;
+      const route = await takePendingDeepLink();
+      if (route && !cancelled) openDeepLinkRef.current(route);
+    })();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [openDeepLinkRef]);
+
+  useEffect(() => {
+    if (!isTauriEnvironment()) return;
+
+    let cancelled = false;
+    const unlisteners: Array<() => void> = [];
+
+    (async () => {
+      try {
+        // Scoped to this window: several chat windows can be open and the
+        // shell targets most events at one of them
+        const { getCurrentWebviewWindow } = await import(
+          "@tauri-apps/api/webviewWindow"
+        );
+        const currentWindow = getCurrentWebviewWindow();
+        for (const event of Object.keys(handlersRef.current)) {
+          const unlisten = await currentWindow.listen(event, ({ payload }) =>
+            handlersRef.current[event]?.(payload),
+          );
+          if (cancelled) {
+            unlisten();
+            return;
+          }
+          unlisteners.push(unlisten);
+        }
+      } catch (err) {
+        console.error("[Tauri] Failed to listen for desktop events:", err);
+      }
+    })();
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, [handlersRef]);
+}
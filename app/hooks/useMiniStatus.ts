@@ -0,0 +1,88 @@
+"use client";
+
+import { useEffect, useRef } from "react";
+import type { UseChatHelpers } from "@ai-sdk/react";
+import type { ChatMessage, Todo } from "@/types/chat";
+import {
+  isTauriEnvironment,
+  updateMiniStatus,
+  type MiniStatus,
+} from "@/app/hooks/useTauri";
+
+const MAX_MESSAGE_LENGTH = 200;
+
+export interface UseMiniStatusParams {
+  chatTitle: string | null;
+  messages: ChatMessage[];
+  status: UseChatHelpers<ChatMessage>["status"];
+  todos: Todo[];
+}
+
+function latestText(
+  messages: ChatMessage[],
+  role: ChatMessage["role"],
+): string | null {
+  for (let i = messages.length - 1; i >= 0; i--) {
+    const message = messages[i];
+    if (message.role !== role) continue;
+    const text = message.parts
+      .map((part) => (part.type === "text" ? part.text : ""))
+      .join(" ")
+      .replace(/\s+/g, " ")
+      .trim();
+    if (!text) continue;
+    return text.length > MAX_MESSAGE_LENGTH
+      ? `${text.slice(0, MAX_MESSAGE_LENGTH - 1)}…`
+      : text;
+  }
+  return null;
+}
+
+/**
+ * Summarize the chat for the mini window. While a response is in flight the
+ * last message is the request being worked on, so the payload only changes
+ * on status transitions rather than on every streamed chunk.
+ */
+export function buildMiniStatus({
+  chatTitle,
+  messages,
+  status,
+  todos,
+}: UseMiniStatusParams): MiniStatus {
+  const running = status === "submitted" || status === "streaming";
+  const tracked = todos.filter((todo) => todo.status !== "cancelled");
+  const completed = tracked.filter((todo) => todo.status === "completed");
+
+  return {
+    status: running
+      ? "running"
+      : status === "error"
+        ? "error"
+        : messages.length > 0
+          ? "done"
+          : null,
+    chatTitle,
+    lastMessage: running
+      ? latestText(messages, "user")
+      : latestText(messages, "assistant"),
+    progress: tracked.length > 0 ? completed.length / tracked.length : null,
+  };
+}
+
+/**
+ * Feed the desktop mini window with the active chat's agent state.
+ * No-op outside the desktop app.
+ */
+export function useMiniStatus(params: UseMiniStatusParams) {
+  const lastSentRef = useRef<string | null>(null);
+  const miniStatus = isTauriEnvironment() ? buildMiniStatus(params) : null;
+  const serialized = miniStatus ? JSON.stringify(miniStatus) : null;
+
+  useEffect(() => {
+    if (!miniStatus || serialized === lastSentRef.current) return;
+    lastSentRef.current = serialized;
+    void updateMiniStatus(miniStatus);
+    // miniStatus is rebuilt every render; serialized tracks its content
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [serialized]);
+}
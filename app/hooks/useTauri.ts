@@ -169,6 +169,90 @@ export async function getCmdServerInfo(): Promise<{
   }
 }
 
+/**
+ * Agent state shown in the desktop mini window. Fields left out render as
+ * the window's idle defaults.
+ */
+export type MiniStatus = {
+  status?: string | null;
+  chatTitle?: string | null;
+  lastMessage?: string | null;
+  /** Completion in 0–1 when known. */
+  progress?: number | null;
+};
+
+export async function updateMiniStatus(status: MiniStatus): Promise<void> {
+  if (!detectTauri()) return;
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("update_mini_status", { status });
+  } catch (err) {
+    // Older desktop builds don't have the mini window
+    console.error("[Tauri] Failed to update mini status:", err);
+  }
+}
+
+export type AgentEventKind = "completed" | "failed" | "approval_required";
+
+/**
+ * Show a native notification for an agent event. The desktop app skips it
+ * when this window is already in front. Resolves to whether one was shown.
+ */
+export async function notifyAgentEvent(
+  kind: AgentEventKind,
+  chatTitle: string | null,
+  chatId: string | null,
+): Promise<boolean> {
+  if (!detectTauri()) return false;
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<boolean>("notify_agent_event", {
+      kind,
+      chatTitle,
+      chatId,
+    });
+  } catch (err) {
+    console.error("[Tauri] Failed to notify agent event:", err);
+    return false;
+  }
+}
+
+/** Set the dock / taskbar badge. A count of 0 clears it. */
+export async function setBadgeCount(count: number): Promise<void> {
+  if (!detectTauri()) return;
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("set_badge_count", { count });
+  } catch (err) {
+    console.error("[Tauri] Failed to set badge count:", err);
+  }
+}
+
+/** In-app destination from a `hackerai://` link. */
+export type DeepLinkRoute =
+  | { view: "chat"; chatId: string }
+  | { view: "new-chat"; prompt: string | null }
+  | { view: "settings"; section: string | null };
+
+/**
+ * Take the deep link the desktop app is holding for this window, if any.
+ * Taking it acknowledges it, so a reload doesn't open it again.
+ */
+export async function takePendingDeepLink(): Promise<DeepLinkRoute | null> {
+  if (!detectTauri()) return null;
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<DeepLinkRoute | null>("take_pending_deep_link");
+  } catch (err) {
+    console.error("[Tauri] Failed to take pending deep link:", err);
+    return null;
+  }
+}
+
 export type LocalFileMetadata = {
   path: string;
   name: string;
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+
+use crate::{settings, tray};
+
+/// Passed when the OS launches the app at login, so a manual launch always
+/// shows the window even with `start_minimized` set.
+const AUTOSTART_ARG: &str = "--autostart";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartInfo {
+    pub enabled: bool,
+    pub start_minimized: bool,
+}
+
+/// Login item registration: a LaunchAgent on macOS, the Run registry key on
+/// Windows, and an XDG autostart entry on Linux.
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec![AUTOSTART_ARG]))
+}
+
+pub fn info(app: &AppHandle) -> Result<AutostartInfo, String> {
+    let enabled = app
+        .autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to read login item: {}", e))?;
+    Ok(AutostartInfo {
+        enabled,
+        start_minimized: settings::load(app).start_minimized,
+    })
+}
+
+/// Register or remove the login item. `start_minimized` is left unchanged
+/// when `None`.
+pub fn set(
+    app: &AppHandle,
+    enabled: bool,
+    start_minimized: Option<bool>,
+) -> Result<AutostartInfo, String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| format!("Failed to update login item: {}", e))?;
+
+    if let Some(start_minimized) = start_minimized {
+        settings::update(app, |settings| settings.start_minimized = start_minimized)?;
+    }
+    info(app)
+}
+
+/// Hide the main window when launched at login with `start_minimized`. Only
+/// applies while the tray icon exists, otherwise the app would be unreachable.
+pub fn apply_launch_state(app: &AppHandle) {
+    let launched_at_login = std::env::args().any(|arg| arg == AUTOSTART_ARG);
+    if !launched_at_login || !settings::load(app).start_minimized || !tray::is_active(app) {
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        log::info!("Launched at login, starting minimized to tray");
+        let _ = window.hide();
+    }
+}
@@ -0,0 +1,49 @@
+use tauri::{AppHandle, Manager};
+
+/// Show `count` on the dock / launcher icon, or clear it when zero.
+///
+/// - **macOS / Linux:** native badge count (Linux needs a launcher that
+///   supports the Unity LauncherEntry API, e.g. GNOME with Dash to Dock).
+/// - **Windows:** taskbar overlay dot; overlays can't render a number.
+pub fn set(app: &AppHandle, count: u32) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window is unavailable")?;
+    let count = (count > 0).then_some(count);
+
+    #[cfg(target_os = "windows")]
+    {
+        window
+            .set_overlay_icon(count.map(|_| overlay_dot()))
+            .map_err(|e| format!("Failed to set taskbar overlay: {}", e))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        window
+            .set_badge_count(count.map(i64::from))
+            .map_err(|e| format!("Failed to set badge count: {}", e))
+    }
+}
+
+/// 16x16 red dot used as the Windows taskbar overlay.
+#[cfg(target_os = "windows")]
+fn overlay_dot() -> tauri::image::Image<'static> {
+    const SIZE: u32 = 16;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let radius = SIZE as f32 / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let dx = x as f32 - center;
+            let dy = y as f32 - center;
+            let pixel = if dx * dx + dy * dy <= radius * radius {
+                [0xE5, 0x48, 0x4D, 0xFF]
+            } else {
+                [0, 0, 0, 0]
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    tauri::image::Image::new_owned(rgba, SIZE, SIZE)
+}
@@ -0,0 +1,68 @@
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::{downloads, theme, zoom};
+
+/// Label prefix for additional chat windows. Must stay in sync with the
+/// `windows` list in `capabilities/default.json`.
+pub const LABEL_PREFIX: &str = "chat-";
+
+/// Resolve `path` against the origin the main window is showing, refusing
+/// anything that would navigate to a different origin.
+fn resolve_url(app: &AppHandle, path: &str) -> Result<url::Url, String> {
+    let main = app
+        .get_webview_window("main")
+        .ok_or("Main window is unavailable")?;
+    let current = main
+        .url()
+        .map_err(|e| format!("Failed to read main window URL: {}", e))?;
+    let target = current
+        .join(path)
+        .map_err(|e| format!("Invalid path '{}': {}", path, e))?;
+    if target.origin() != current.origin() {
+        return Err(format!(
+            "Refusing to open '{}' outside {}",
+            path,
+            current.origin().ascii_serialization()
+        ));
+    }
+    Ok(target)
+}
+
+/// The user agent configured for the main window, so the web app recognizes
+/// secondary windows as the desktop client too.
+fn configured_user_agent(app: &AppHandle) -> Option<String> {
+    app.config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == "main")
+        .and_then(|window| window.user_agent.clone())
+}
+
+/// Open another webview window at `path` (e.g. `/c/<chat id>`) on the main
+/// window's origin. Returns the new window's label.
+pub fn open(app: &AppHandle, path: Option<&str>) -> Result<String, String> {
+    let url = resolve_url(app, path.unwrap_or("/"))?;
+    let label = format!("{}{}", LABEL_PREFIX, uuid::Uuid::new_v4().simple());
+
+    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::External(url))
+        .title("HackerAI")
+        .inner_size(1280.0, 800.0)
+        .min_inner_size(900.0, 600.0)
+        .theme(theme::preference(app).forced())
+        .disable_drag_drop_handler()
+        .on_download(downloads::handle_webview_download);
+    if let Some(user_agent) = configured_user_agent(app) {
+        builder = builder.user_agent(&user_agent);
+    }
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open window: {}", e))?;
+
+    let level = zoom::current(app);
+    if level != 1.0 {
+        let _ = window.set_zoom(level);
+    }
+
+    Ok(label)
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+/// Present while the app is running; finding it on launch means the previous
+/// session ended without a clean exit (native crash, force quit, power loss).
+const SESSION_MARKER: &str = "session.lock";
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Route on the backend origin that receives reports.
+const UPLOAD_PATH: &str = "/api/desktop/crash-reports";
+
+/// `<app log dir>/crashes`, set once the hook is installed.
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrashKind {
+    Panic,
+    UncleanExit,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: CrashKind,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: Option<String>,
+    /// When the user sent this report, in milliseconds since the Unix epoch.
+    pub uploaded_at: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+    pub id: String,
+    pub kind: CrashKind,
+    pub created_at: u64,
+    pub app_version: String,
+    pub message: String,
+    pub uploaded_at: Option<u64>,
+}
+
+fn now_millis() -> u64 {
+    crate::unix_millis(SystemTime::now())
+}
+
+fn new_report(kind: CrashKind, app_version: &str, message: String) -> CrashReport {
+    CrashReport {
+        id: format!("{}-{}", now_millis(), uuid::Uuid::new_v4().simple()),
+        kind,
+        created_at: now_millis(),
+        app_version: app_version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: None,
+        message,
+        location: None,
+        backtrace: None,
+        uploaded_at: None,
+    }
+}
+
+fn write_report(dir: &Path, report: &CrashReport) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let json = serde_json::to_vec_pretty(report)?;
+    fs::write(dir.join(format!("{}.json", report.id)), json)
+}
+
+/// Start writing crash reports: a panic hook that records the message,
+/// location, and backtrace, plus a session marker that turns the next launch
+/// into an "unclean exit" report if this session dies without one (e.g. a
+/// native crash, which the panic hook can't see).
+pub fn install(app: &AppHandle) {
+    let dir = match app.path().app_log_dir() {
+        Ok(dir) => dir.join("crashes"),
+        Err(e) => {
+            log::warn!("Crash reporting disabled: {}", e);
+            return;
+        }
+    };
+    let app_version = app.package_info().version.to_string();
+
+    let marker = dir.join(SESSION_MARKER);
+    if marker.exists() {
+        let report = new_report(
+            CrashKind::UncleanExit,
+            &app_version,
+            "The previous session ended without a clean exit".to_string(),
+        );
+        match write_report(&dir, &report) {
+            Ok(()) => log::warn!("Previous session did not exit cleanly ({})", report.id),
+            Err(e) => log::warn!("Failed to write crash report: {}", e),
+        }
+    }
+    if let Err(e) =
+        fs::create_dir_all(&dir).and_then(|()| fs::write(&marker, std::process::id().to_string()))
+    {
+        log::warn!("Failed to write session marker: {}", e);
+    }
+
+    let hook_dir = dir.clone();
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        let mut report = new_report(CrashKind::Panic, &app_version, message);
+        report.thread = std::thread::current().name().map(str::to_string);
+        report.location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        report.backtrace = Some(std::backtrace::Backtrace::force_capture().to_string());
+        if write_report(&hook_dir, &report).is_ok() {
+            // Already reported; don't add an unclean-exit report on top
+            let _ = fs::remove_file(hook_dir.join(SESSION_MARKER));
+        }
+        previous(info);
+    }));
+
+    let _ = CRASH_DIR.set(dir);
+}
+
+/// Remove the session marker. Call on every intentional exit, including
+/// restarts for updates, which skip `RunEvent::Exit`.
+pub fn mark_clean_exit() {
+    if let Some(dir) = CRASH_DIR.get() {
+        let _ = fs::remove_file(dir.join(SESSION_MARKER));
+    }
+}
+
+fn crash_dir() -> Result<&'static PathBuf, String> {
+    CRASH_DIR
+        .get()
+        .ok_or_else(|| "Crash reporting is unavailable".to_string())
+}
+
+fn report_path(id: &str) -> Result<PathBuf, String> {
+    let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    Ok(crash_dir()?.join(format!("{}.json", id)))
+}
+
+pub fn get(id: &str) -> Result<CrashReport, String> {
+    let content = fs::read(report_path(id)?).map_err(|e| format!("Read error: {}", e))?;
+    serde_json::from_slice(&content).map_err(|e| format!("Parse error: {}", e))
+}
+
+/// All saved reports, newest first.
+pub fn list() -> Result<Vec<CrashReportSummary>, String> {
+    let dir = crash_dir()?;
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Read error: {}", e)),
+    };
+
+    let mut reports: Vec<CrashReportSummary> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read(&path).ok())
+        .filter_map(|content| serde_json::from_slice::<CrashReport>(&content).ok())
+        .map(|report| CrashReportSummary {
+            id: report.id,
+            kind: report.kind,
+            created_at: report.created_at,
+            app_version: report.app_version,
+            message: report.message,
+            uploaded_at: report.uploaded_at,
+        })
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(reports)
+}
+
+pub fn delete(id: &str) -> Result<(), String> {
+    fs::remove_file(report_path(id)?).map_err(|e| format!("Delete error: {}", e))
+}
+
+/// Send a report to the backend. Nothing is ever uploaded automatically; the
+/// caller must pass `consent` after the user has reviewed the report.
+pub async fn upload(app: &AppHandle, id: &str, consent: bool) -> Result<CrashReport, String> {
+    if !consent {
+        return Err("Uploading a crash report requires the user's consent".to_string());
+    }
+    let mut report = get(id)?;
+
+    crate::http::client(app)?
+        .post(format!("{}{}", crate::get_default_origin(), UPLOAD_PATH))
+        .timeout(UPLOAD_TIMEOUT)
+        .json(&report)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Upload error: {}", e))?;
+
+    report.uploaded_at = Some(now_millis());
+    write_report(crash_dir()?, &report).map_err(|e| format!("Write error: {}", e))?;
+    log::info!("Uploaded crash report {}", report.id);
+    Ok(report)
+}
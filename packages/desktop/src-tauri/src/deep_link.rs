@@ -0,0 +1,220 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::tray;
+
+/// Longest prompt accepted from a `new-chat` link. Links are often pasted from
+/// untrusted places, so the prompt is only ever prefilled, never submitted.
+const MAX_PROMPT_LEN: usize = 8_000;
+const MAX_ID_LEN: usize = 128;
+
+/// In-app destination parsed from a `hackerai://` link.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "view", rename_all = "kebab-case")]
+pub enum DeepLinkRoute {
+    #[serde(rename_all = "camelCase")]
+    Chat {
+        chat_id: String,
+    },
+    NewChat {
+        prompt: Option<String>,
+    },
+    Settings {
+        section: Option<String>,
+    },
+}
+
+impl DeepLinkRoute {
+    /// Name of the destination view, safe to log: unlike the route itself it
+    /// carries no user-supplied text.
+    pub fn view(&self) -> &'static str {
+        match self {
+            DeepLinkRoute::Chat { .. } => "chat",
+            DeepLinkRoute::NewChat { .. } => "new-chat",
+            DeepLinkRoute::Settings { .. } => "settings",
+        }
+    }
+}
+
+/// Route received before the webview could handle it, e.g. the link that
+/// launched the app. Taken by the webview once it has loaded.
+#[derive(Default)]
+pub struct PendingDeepLink(Mutex<Option<DeepLinkRoute>>);
+
+fn is_valid_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_ID_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parse `hackerai://chat/<id>`, `hackerai://new-chat?prompt=...`, and
+/// `hackerai://settings[/<section>]`. Returns `None` for anything else,
+/// including auth links.
+pub fn parse(url: &url::Url) -> Option<DeepLinkRoute> {
+    if url.scheme() != "hackerai" {
+        return None;
+    }
+
+    // `hackerai://chat/x` puts "chat" in the host; `hackerai:chat/x` doesn't
+    let mut segments = url
+        .host_str()
+        .into_iter()
+        .chain(url.path().split('/'))
+        .filter(|segment| !segment.is_empty());
+    let view = segments.next()?;
+    let arg = segments.next();
+    if segments.next().is_some() {
+        return None;
+    }
+
+    match (view, arg) {
+        ("chat", Some(id)) if is_valid_id(id) => Some(DeepLinkRoute::Chat {
+            chat_id: id.to_string(),
+        }),
+        ("new-chat", None) => {
+            let prompt = url
+                .query_pairs()
+                .find(|(k, _)| k == "prompt")
+                .map(|(_, v)| v.trim().chars().take(MAX_PROMPT_LEN).collect::<String>())
+                .filter(|prompt| !prompt.is_empty());
+            Some(DeepLinkRoute::NewChat { prompt })
+        }
+        ("settings", None) => Some(DeepLinkRoute::Settings { section: None }),
+        ("settings", Some(section)) if is_valid_id(section) => Some(DeepLinkRoute::Settings {
+            section: Some(section.to_string()),
+        }),
+        _ => None,
+    }
+}
+
+/// Bring the main window forward and forward `route` to it as a
+/// `deep-link-navigate` event. The route is also kept until the webview takes
+/// it, so a link that launched the app isn't lost while the page loads.
+pub fn navigate(app: &AppHandle, route: DeepLinkRoute) {
+    tray::show_main_window(app);
+    if let Some(pending) = app.try_state::<PendingDeepLink>() {
+        if let Ok(mut pending) = pending.0.lock() {
+            *pending = Some(route.clone());
+        }
+    }
+    if let Err(e) = app.emit_to("main", "deep-link-navigate", route) {
+        log::warn!("Failed to forward deep link: {}", e);
+    }
+}
+
+/// Take the route that hasn't been acknowledged by the webview yet, if any.
+pub fn take_pending(app: &AppHandle) -> Option<DeepLinkRoute> {
+    app.try_state::<PendingDeepLink>()
+        .and_then(|pending| pending.0.lock().ok().and_then(|mut route| route.take()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(link: &str) -> Option<DeepLinkRoute> {
+        parse(&url::Url::parse(link).expect("valid url"))
+    }
+
+    fn chat(id: &str) -> Option<DeepLinkRoute> {
+        Some(DeepLinkRoute::Chat {
+            chat_id: id.to_string(),
+        })
+    }
+
+    #[test]
+    fn parses_chat_links() {
+        assert_eq!(parse_str("hackerai://chat/abc-123_X"), chat("abc-123_X"));
+        assert_eq!(parse_str("hackerai://chat/abc/"), chat("abc"));
+        assert_eq!(parse_str("hackerai:chat/x"), chat("x"));
+    }
+
+    #[test]
+    fn rejects_invalid_chat_ids() {
+        assert_eq!(parse_str("hackerai://chat"), None);
+        assert_eq!(parse_str("hackerai://chat/"), None);
+        assert_eq!(parse_str("hackerai://chat/a.b"), None);
+        assert_eq!(parse_str("hackerai://chat/%2e%2e"), None);
+        assert_eq!(parse_str("hackerai://chat/a%20b"), None);
+        let too_long = "a".repeat(MAX_ID_LEN + 1);
+        assert_eq!(parse_str(&format!("hackerai://chat/{}", too_long)), None);
+        let longest = "a".repeat(MAX_ID_LEN);
+        assert_eq!(
+            parse_str(&format!("hackerai://chat/{}", longest)),
+            chat(&longest)
+        );
+    }
+
+    #[test]
+    fn rejects_extra_segments() {
+        assert_eq!(parse_str("hackerai://chat/x/y"), None);
+        assert_eq!(parse_str("hackerai://new-chat/x"), None);
+        assert_eq!(parse_str("hackerai://settings/a/b"), None);
+    }
+
+    #[test]
+    fn parses_settings_links() {
+        assert_eq!(
+            parse_str("hackerai://settings"),
+            Some(DeepLinkRoute::Settings { section: None })
+        );
+        assert_eq!(
+            parse_str("hackerai://settings/account"),
+            Some(DeepLinkRoute::Settings {
+                section: Some("account".to_string())
+            })
+        );
+        assert_eq!(parse_str("hackerai://settings/a.b"), None);
+    }
+
+    #[test]
+    fn leaves_auth_and_unknown_links_to_other_handlers() {
+        let token = "a".repeat(64);
+        assert_eq!(parse_str(&format!("hackerai://auth?token={}", token)), None);
+        assert_eq!(parse_str(&format!("hackerai:auth?token={}", token)), None);
+        assert_eq!(parse_str("hackerai://unknown/x"), None);
+        assert_eq!(parse_str("hackerai://"), None);
+        assert_eq!(parse_str("https://chat/x"), None);
+    }
+
+    #[test]
+    fn parses_new_chat_prompt() {
+        assert_eq!(
+            parse_str("hackerai://new-chat"),
+            Some(DeepLinkRoute::NewChat { prompt: None })
+        );
+        assert_eq!(
+            parse_str("hackerai://new-chat?prompt=%20%20"),
+            Some(DeepLinkRoute::NewChat { prompt: None })
+        );
+        assert_eq!(
+            parse_str("hackerai://new-chat?prompt=%20scan%20example.com%20"),
+            Some(DeepLinkRoute::NewChat {
+                prompt: Some("scan example.com".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn truncates_long_prompts() {
+        let prompt = "é".repeat(MAX_PROMPT_LEN + 10);
+        let link = url::Url::parse_with_params("hackerai://new-chat", [("prompt", &prompt)])
+            .expect("valid url");
+        let Some(DeepLinkRoute::NewChat {
+            prompt: Some(parsed),
+        }) = parse(&link)
+        else {
+            panic!("expected a new-chat route");
+        };
+        assert_eq!(parsed.chars().count(), MAX_PROMPT_LEN);
+    }
+
+    #[test]
+    fn view_names_carry_no_user_text() {
+        let route = parse_str("hackerai://new-chat?prompt=secret").expect("route");
+        assert_eq!(route.view(), "new-chat");
+    }
+}
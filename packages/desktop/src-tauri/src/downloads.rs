@@ -0,0 +1,478 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::webview::DownloadEvent;
+use tauri::{AppHandle, Emitter, Manager, Webview};
+use tauri_plugin_opener::OpenerExt;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::oneshot;
+
+use crate::{http, settings};
+
+/// Minimum time between `download-progress` events for one download.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+pub struct DownloadState(Mutex<Downloads>);
+
+#[derive(Default)]
+struct Downloads {
+    /// Webview downloads in flight (URL -> id and destination); the finish
+    /// event only carries the URL, and not always the path.
+    in_flight: HashMap<String, (String, PathBuf)>,
+    /// Files written by a finished download. `open` and `reveal` are limited
+    /// to these so the webview can't launch arbitrary files.
+    completed: HashSet<PathBuf>,
+    /// `download_file` transfers in flight (id -> cancel signal).
+    active: HashMap<String, oneshot::Sender<()>>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadStartedEvent {
+    id: String,
+    url: String,
+    file_name: String,
+    path: String,
+    total_bytes: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgressEvent {
+    id: String,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadFinishedEvent {
+    id: String,
+    path: Option<String>,
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadResult {
+    pub id: String,
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Configured downloads directory, falling back to the system one.
+pub fn download_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = match settings::load(app).download_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .download_dir()
+            .map_err(|e| format!("Downloads directory is unavailable: {}", e))?,
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Mkdir error: {}", e))?;
+    Ok(dir)
+}
+
+/// Change the downloads directory; `None` restores the system default.
+pub fn set_download_dir(app: &AppHandle, dir: Option<String>) -> Result<String, String> {
+    if let Some(ref dir) = dir {
+        let path = Path::new(dir);
+        if !path.is_absolute() {
+            return Err(format!("Download directory must be absolute: {}", dir));
+        }
+        std::fs::create_dir_all(path).map_err(|e| format!("Mkdir error: {}", e))?;
+    }
+    settings::update(app, |settings| settings.download_dir = dir)?;
+    download_dir(app).map(|dir| dir.display().to_string())
+}
+
+/// Keep only the final path component and replace characters that aren't
+/// valid in file names on every platform.
+fn sanitize_file_name(name: &str) -> String {
+    let name = Path::new(name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn file_name_from_url(url: &url::Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|segment| !segment.is_empty())
+        .unwrap_or("download")
+        .to_string()
+}
+
+/// `dir/name`, then `dir/name (1).ext`, `dir/name (2).ext`, ...
+fn candidate_paths<'a>(dir: &'a Path, name: &'a str) -> impl Iterator<Item = PathBuf> + 'a {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("download");
+    let extension = path.extension().and_then(|s| s.to_str());
+    std::iter::once(dir.join(name)).chain((1..).map(move |n| match extension {
+        Some(ext) => dir.join(format!("{} ({}).{}", stem, n, ext)),
+        None => dir.join(format!("{} ({})", stem, n)),
+    }))
+}
+
+/// First candidate path that doesn't exist yet.
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    candidate_paths(dir, name)
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| dir.join(name))
+}
+
+/// Like `unique_path`, but claims the path by creating an empty file, so
+/// concurrent downloads of the same name can't pick the same destination.
+fn reserve_path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    for candidate in candidate_paths(dir, name) {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(_) => return Ok(candidate),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Create error: {}", e)),
+        }
+    }
+    Err(format!("No free file name for {}", name))
+}
+
+fn record_completed(app: &AppHandle, path: &Path) {
+    if let Some(state) = app.try_state::<DownloadState>() {
+        if let Ok(mut downloads) = state.0.lock() {
+            downloads.completed.insert(path.to_path_buf());
+        }
+    }
+}
+
+/// `on_download` handler for webview windows. Downloads the webview starts
+/// itself (links, blobs) keep its cookies and are redirected into the
+/// downloads directory; platform webviews don't report their progress, so
+/// only `download-started` and `download-finished` are emitted for them.
+pub fn handle_webview_download(webview: Webview, event: DownloadEvent<'_>) -> bool {
+    let app = webview.app_handle();
+    match event {
+        DownloadEvent::Requested { url, destination } => {
+            let dir = match download_dir(app) {
+                Ok(dir) => dir,
+                Err(e) => {
+                    log::warn!("Using the webview's download location: {}", e);
+                    return true;
+                }
+            };
+            let name = destination
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| file_name_from_url(&url));
+            let file_name = sanitize_file_name(&name);
+            let path = unique_path(&dir, &file_name);
+            *destination = path.clone();
+
+            let id = uuid::Uuid::new_v4().to_string();
+            if let Some(state) = app.try_state::<DownloadState>() {
+                if let Ok(mut downloads) = state.0.lock() {
+                    downloads
+                        .in_flight
+                        .insert(url.to_string(), (id.clone(), path.clone()));
+                }
+            }
+            let _ = app.emit(
+                "download-started",
+                DownloadStartedEvent {
+                    id,
+                    url: url.to_string(),
+                    file_name,
+                    path: path.display().to_string(),
+                    total_bytes: None,
+                },
+            );
+            true
+        }
+        DownloadEvent::Finished { url, path, success } => {
+            let in_flight = app.try_state::<DownloadState>().and_then(|state| {
+                state
+                    .0
+                    .lock()
+                    .ok()
+                    .and_then(|mut d| d.in_flight.remove(url.as_str()))
+            });
+            let (id, path) = match in_flight {
+                Some((id, requested)) => (id, path.or(Some(requested))),
+                None => (uuid::Uuid::new_v4().to_string(), path),
+            };
+            if let (true, Some(path)) = (success, path.as_ref()) {
+                record_completed(app, path);
+            }
+            let _ = app.emit(
+                "download-finished",
+                DownloadFinishedEvent {
+                    id,
+                    path: path.map(|p| p.display().to_string()),
+                    success,
+                    error: (!success).then(|| "Download failed".to_string()),
+                },
+            );
+            true
+        }
+        _ => true,
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Stream the response body into the reserved `part_path`, emitting
+/// progress, until it ends or `cancel` fires. Returns the size and SHA-256 of
+/// what was written.
+async fn fetch(
+    app: &AppHandle,
+    id: &str,
+    mut response: reqwest::Response,
+    part_path: &Path,
+    total_bytes: Option<u64>,
+    mut cancel: oneshot::Receiver<()>,
+) -> Result<(u64, String), String> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(part_path)
+        .await
+        .map_err(|e| format!("Open error: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut downloaded: u64 = 0;
+    let mut last_progress = Instant::now();
+
+    loop {
+        let chunk = tokio::select! {
+            chunk = response.chunk() => chunk.map_err(|e| format!("Download error: {}", e))?,
+            _ = &mut cancel => return Err("Download cancelled".to_string()),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            let _ = app.emit(
+                "download-progress",
+                DownloadProgressEvent {
+                    id: id.to_string(),
+                    downloaded_bytes: downloaded,
+                    total_bytes,
+                },
+            );
+        }
+    }
+    file.flush()
+        .await
+        .map_err(|e| format!("Write error: {}", e))?;
+
+    Ok((downloaded, to_hex(&hasher.finalize())))
+}
+
+/// Download `url` into the downloads directory with progress events, then
+/// verify it against `expected_size` / `expected_sha256` when given. The
+/// destination is reserved up front; data goes to `<name>.part`, which only
+/// replaces it once it checks out.
+pub async fn download(
+    app: &AppHandle,
+    url: &str,
+    file_name: Option<String>,
+    expected_size: Option<u64>,
+    expected_sha256: Option<String>,
+) -> Result<DownloadResult, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "https" && parsed.scheme() != "http" {
+        return Err(format!("Unsupported URL scheme: {}", parsed.scheme()));
+    }
+
+    let response = http::client(app)?
+        .get(parsed.clone())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Download error: {}", e))?;
+    let total_bytes = expected_size.or(response.content_length());
+
+    let dir = download_dir(app)?;
+    let file_name = sanitize_file_name(&file_name.unwrap_or_else(|| file_name_from_url(&parsed)));
+    let path = reserve_path(&dir, &file_name)?;
+    // Reserved the same way, so a `.part` left by something else is never
+    // overwritten or deleted
+    let part_name = format!(
+        "{}.part",
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("download")
+    );
+    let part_path = match reserve_path(&dir, &part_name) {
+        Ok(part_path) => part_path,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(e);
+        }
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    if let Some(state) = app.try_state::<DownloadState>() {
+        if let Ok(mut downloads) = state.0.lock() {
+            downloads.active.insert(id.clone(), cancel_tx);
+        }
+    }
+    let _ = app.emit(
+        "download-started",
+        DownloadStartedEvent {
+            id: id.clone(),
+            url: url.to_string(),
+            file_name,
+            path: path.display().to_string(),
+            total_bytes,
+        },
+    );
+
+    let result = async {
+        let (size, sha256) = fetch(app, &id, response, &part_path, total_bytes, cancel_rx).await?;
+        if let Some(expected) = expected_size {
+            if size != expected {
+                return Err(format!(
+                    "Size mismatch: expected {} bytes, got {}",
+                    expected, size
+                ));
+            }
+        }
+        if let Some(ref expected) = expected_sha256 {
+            if !expected.trim().eq_ignore_ascii_case(&sha256) {
+                return Err(format!(
+                    "Checksum mismatch: expected {}, got {}",
+                    expected, sha256
+                ));
+            }
+        }
+        tokio::fs::rename(&part_path, &path)
+            .await
+            .map_err(|e| format!("Rename error: {}", e))?;
+        Ok::<_, String>((size, sha256))
+    }
+    .await;
+
+    if let Some(state) = app.try_state::<DownloadState>() {
+        if let Ok(mut downloads) = state.0.lock() {
+            downloads.active.remove(&id);
+        }
+    }
+
+    match result {
+        Ok((size, sha256)) => {
+            record_completed(app, &path);
+            let _ = app.emit(
+                "download-finished",
+                DownloadFinishedEvent {
+                    id: id.clone(),
+                    path: Some(path.display().to_string()),
+                    success: true,
+                    error: None,
+                },
+            );
+            Ok(DownloadResult {
+                id,
+                path: path.display().to_string(),
+                size,
+                sha256,
+            })
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            let _ = tokio::fs::remove_file(&path).await;
+            let _ = app.emit(
+                "download-finished",
+                DownloadFinishedEvent {
+                    id,
+                    path: None,
+                    success: false,
+                    error: Some(e.clone()),
+                },
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Stop a `download` in flight; it then fails with "Download cancelled".
+/// Returns whether `id` was still running.
+pub fn cancel(app: &AppHandle, id: &str) -> Result<bool, String> {
+    let state = app
+        .try_state::<DownloadState>()
+        .ok_or("Download state is unavailable")?;
+    let cancel = state
+        .0
+        .lock()
+        .map_err(|_| "download state poisoned".to_string())?
+        .active
+        .remove(id);
+    Ok(cancel.is_some_and(|cancel| cancel.send(()).is_ok()))
+}
+
+fn completed_path(app: &AppHandle, path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+    let known = app
+        .try_state::<DownloadState>()
+        .and_then(|state| {
+            state
+                .0
+                .lock()
+                .ok()
+                .map(|downloads| downloads.completed.contains(&path))
+        })
+        .unwrap_or(false);
+    if !known {
+        return Err(format!("Not a finished download: {}", path.display()));
+    }
+    Ok(path)
+}
+
+/// Open a finished download with its default application.
+pub fn open(app: &AppHandle, path: &str) -> Result<(), String> {
+    let path = completed_path(app, path)?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Show a finished download in the file manager.
+pub fn reveal(app: &AppHandle, path: &str) -> Result<(), String> {
+    let path = completed_path(app, path)?;
+    app.opener()
+        .reveal_item_in_dir(&path)
+        .map_err(|e| format!("Failed to reveal {}: {}", path.display(), e))
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Longest wait for more response data, so a stalled transfer fails instead
+/// of hanging. Callers add an overall `timeout` per request where it fits.
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The HTTP client for all Rust-side requests, configured once and kept as
+/// Tauri state so connections are pooled. Proxies come from the environment
+/// (`HTTPS_PROXY`, `NO_PROXY`, ...), which reqwest reads by default.
+pub struct HttpClient(reqwest::Client);
+
+impl HttpClient {
+    pub fn new(app: &AppHandle) -> Result<Self, String> {
+        let user_agent = format!("HackerAI-Desktop/{}", app.package_info().version);
+        reqwest::Client::builder()
+            .user_agent(user_agent)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
+            .build()
+            .map(Self)
+            .map_err(|e| format!("HTTP client error: {}", e))
+    }
+}
+
+/// The shared client. Cloning is cheap; clones share the connection pool.
+pub fn client(app: &AppHandle) -> Result<reqwest::Client, String> {
+    app.try_state::<HttpClient>()
+        .map(|client| client.0.clone())
+        .ok_or_else(|| "HTTP client is unavailable".to_string())
+}
@@ -1,5 +1,25 @@
+mod autostart;
+mod badge;
+mod chat_window;
+mod crash;
+mod deep_link;
+mod downloads;
+mod http;
+mod logging;
+mod menu;
+mod mini_window;
+mod network;
+mod notifications;
 mod platform;
+mod power;
+mod print;
 mod pty;
+mod screenshot;
+mod settings;
+mod shortcut;
+mod theme;
+mod tray;
+mod zoom;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,15 +27,16 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tauri_plugin_updater::UpdaterExt;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 
 const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60); // 24 hours
 const DESKTOP_AUTH_STATE_TTL: Duration = Duration::from_secs(5 * 60);
 const MAX_PENDING_DESKTOP_AUTH_STATES: usize = 16;
+const DEFAULT_ORIGIN: &str = "https://hackerai.co";
 
-/// Port for the local dev auth callback server (0 = not started)
+/// Port for the loopback auth callback server (0 = not started)
 static DEV_AUTH_PORT: AtomicU16 = AtomicU16::new(0);
 
 /// Port for the command execution server (0 = not started)
@@ -24,6 +45,9 @@ static CMD_SERVER_PORT: AtomicU16 = AtomicU16::new(0);
 /// Session token for authenticating command server requests
 static CMD_SERVER_TOKEN: std::sync::OnceLock<String> = std::sync::OnceLock::new();
 
+/// Web app origin, resolved once at startup (see `init_default_origin`)
+static APP_ORIGIN: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
 struct PendingDesktopAuthStates(std::sync::Mutex<HashMap<String, SystemTime>>);
 
 fn generate_desktop_auth_state() -> String {
@@ -49,7 +73,7 @@ fn prune_expired_auth_states(states: &mut HashMap<String, SystemTime>, now: Syst
     }
 }
 
-/// Get the dev auth callback port (0 if not running in dev mode)
+/// Get the loopback auth callback port (0 if the deep link flow is in use)
 #[tauri::command]
 fn get_dev_auth_port() -> u16 {
     DEV_AUTH_PORT.load(Ordering::Relaxed)
@@ -144,6 +168,7 @@ fn guess_media_type(path: &std::path::Path) -> String {
         "css" => "text/css",
         "xml" => "application/xml",
         "zip" => "application/zip",
+        "pcap" | "pcapng" | "cap" => "application/vnd.tcpdump.pcap",
         "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
         _ => "application/octet-stream",
     }
@@ -163,12 +188,7 @@ fn get_local_file_metadata(path: String) -> Result<LocalFileMetadata, String> {
         .and_then(|name| name.to_str())
         .unwrap_or("file")
         .to_string();
-    let last_modified = metadata
-        .modified()
-        .ok()
-        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
-        .map(|duration| duration.as_millis() as u64)
-        .unwrap_or(0);
+    let last_modified = metadata.modified().map(unix_millis).unwrap_or(0);
 
     Ok(LocalFileMetadata {
         path,
@@ -1161,18 +1181,42 @@ async fn cancel_stream_command(
     }
 }
 
-/// Start a local HTTP server for dev mode auth callbacks.
-/// This replaces deep links which don't work in `tauri dev` on macOS.
-/// Only compiled in debug builds — matches the cfg-gated call site at the
-/// bottom of `run()`. Without this gate, release builds error out with
-/// `dead_code` under `actions-rust-lang/setup-rust-toolchain@v1`'s
-/// `RUSTFLAGS=-D warnings`.
-#[cfg(debug_assertions)]
-async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
+/// Whether release builds should use the loopback auth callback instead of
+/// the `hackerai://` deep link. Custom URL schemes are unreliable on some
+/// Linux desktops and in portable installs, so users can opt in with
+/// `HACKERAI_LOOPBACK_AUTH=1`.
+fn loopback_auth_enabled() -> bool {
+    matches!(
+        std::env::var("HACKERAI_LOOPBACK_AUTH").as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
+/// Cancel the process tree of every in-flight streaming command.
+async fn cancel_all_stream_commands(state: &StreamCommandState) {
+    let pids: Vec<u32> = state
+        .lock()
+        .map(|commands| commands.values().copied().collect())
+        .unwrap_or_default();
+    let cancellations: Vec<_> = pids
+        .into_iter()
+        .map(|pid| tauri::async_runtime::spawn(platform::cancel_process_tree(pid)))
+        .collect();
+    for cancellation in cancellations {
+        let _ = cancellation.await;
+    }
+}
+
+/// Start a local HTTP server for auth callbacks.
+/// Always used in debug builds (deep links don't work in `tauri dev` on
+/// macOS) and in release builds when `loopback_auth_enabled()` is set.
+/// Callbacks go through the same token format and desktop_state checks as
+/// the deep link handler.
+async fn start_loopback_auth_server(app_handle: tauri::AppHandle) {
     let listener = match tokio::net::TcpListener::bind("127.0.0.1:0").await {
         Ok(l) => l,
         Err(e) => {
-            log::error!("Failed to start dev auth server: {}", e);
+            log::error!("Failed to start loopback auth server: {}", e);
             return;
         }
     };
@@ -1180,13 +1224,13 @@ async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
     let port = match listener.local_addr() {
         Ok(addr) => addr.port(),
         Err(e) => {
-            log::error!("Failed to get dev auth server address: {}", e);
+            log::error!("Failed to get loopback auth server address: {}", e);
             return;
         }
     };
     DEV_AUTH_PORT.store(port, Ordering::Relaxed);
     log::info!(
-        "Dev auth callback server listening on http://localhost:{}",
+        "Loopback auth callback server listening on http://localhost:{}",
         port
     );
 
@@ -1194,7 +1238,7 @@ async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
         let (mut stream, _) = match listener.accept().await {
             Ok(conn) => conn,
             Err(e) => {
-                log::warn!("Dev auth server accept error: {}", e);
+                log::warn!("Loopback auth server accept error: {}", e);
                 continue;
             }
         };
@@ -1257,9 +1301,13 @@ async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
                     if is_valid_token_format(t)
                         && consume_pending_desktop_auth_state(&handle, state) =>
                 {
-                    let origin = origin
-                        .filter(|o| validate_origin(o))
-                        .unwrap_or_else(|| "http://localhost:3000".to_string());
+                    let origin = origin.filter(|o| validate_origin(o)).unwrap_or_else(|| {
+                        if cfg!(debug_assertions) {
+                            "http://localhost:3000".to_string()
+                        } else {
+                            get_default_origin()
+                        }
+                    });
 
                     let encoded_token: String =
                         url::form_urlencoded::byte_serialize(t.as_bytes()).collect();
@@ -1270,10 +1318,7 @@ async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
                         origin, encoded_token, encoded_state
                     );
 
-                    log::info!(
-                        "Dev auth: navigating to callback (token: {}...)",
-                        &t[..8.min(t.len())]
-                    );
+                    log::info!("Loopback auth: navigating to callback");
 
                     if let Some(window) = handle.get_webview_window("main") {
                         let _ = window.set_focus();
@@ -1292,7 +1337,8 @@ async fn start_dev_auth_server(app_handle: tauri::AppHandle) {
                     let _ = stream.write_all(response.as_bytes()).await;
                 }
                 _ => {
-                    log::warn!("Dev auth: invalid or missing token/auth state");
+                    log::warn!("Loopback auth: invalid or missing token/auth state");
+                    emit_auth_error(&handle, "invalid_state");
                     let response = "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n";
                     let _ = stream.write_all(response.as_bytes()).await;
                 }
@@ -1345,10 +1391,98 @@ fn save_update_check_timestamp(app: &tauri::AppHandle) {
     }
 }
 
+/// Milliseconds since the Unix epoch, or 0 for earlier times.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Scheme, host, and port of an http(s) URL. Plain http is only accepted for
+/// localhost, as in `validate_origin`.
+fn normalize_origin(value: &str) -> Option<String> {
+    let parsed = url::Url::parse(value.trim()).ok()?;
+    let host = parsed.host_str()?;
+    match parsed.scheme() {
+        "https" => {}
+        "http" if host == "localhost" => {}
+        _ => return None,
+    }
+    Some(parsed.origin().ascii_serialization())
+}
+
+/// The `HACKERAI_ORIGIN` value, then the `serverOrigin` setting, then
+/// hackerai.co. Invalid values are skipped.
+fn resolve_origin(env: Option<&str>, configured: Option<&str>) -> String {
+    env.and_then(normalize_origin)
+        .or_else(|| configured.and_then(normalize_origin))
+        .unwrap_or_else(|| DEFAULT_ORIGIN.to_string())
+}
+
+fn origin_from_env() -> Option<String> {
+    std::env::var("HACKERAI_ORIGIN").ok()
+}
+
+/// Origin of the web app: what the offline page loads, and the fallback when
+/// an auth callback carries no valid origin. Self-hosted deployments set it
+/// with `HACKERAI_ORIGIN` or `serverOrigin` in settings.json. There is no
+/// command for it: the origin is granted the command bridge, so web content
+/// must not be able to change it.
+fn get_default_origin() -> String {
+    APP_ORIGIN
+        .get()
+        .cloned()
+        .unwrap_or_else(|| resolve_origin(origin_from_env().as_deref(), None))
+}
+
+/// Resolve the web app origin for this session and, for a self-hosted
+/// origin, grant it the command bridge access capabilities/default.json
+/// gives hackerai.co.
+fn init_default_origin(app: &tauri::AppHandle) {
+    let origin = resolve_origin(
+        origin_from_env().as_deref(),
+        settings::load(app).server_origin.as_deref(),
+    );
+    if origin != DEFAULT_ORIGIN {
+        log::info!("Using self-hosted origin {}", origin);
+        if let Err(e) = allow_remote_origin(app, &origin) {
+            log::warn!("Failed to allow {}: {}", origin, e);
+        }
+    }
+    let _ = APP_ORIGIN.set(origin);
+}
+
+fn allow_remote_origin(app: &tauri::AppHandle, origin: &str) -> Result<(), String> {
+    let mut capability: serde_json::Value =
+        serde_json::from_str(include_str!("../capabilities/default.json"))
+            .map_err(|e| format!("Parse error: {}", e))?;
+    if let Some(fields) = capability.as_object_mut() {
+        fields.remove("$schema");
+    }
+    capability["identifier"] = "self-hosted".into();
+    capability["local"] = false.into();
+    capability["remote"]["urls"] = serde_json::json!([format!("{}/*", origin)]);
+    app.add_capability(capability.to_string())
+        .map_err(|e| format!("Capability error: {}", e))
+}
+
 fn get_allowed_hosts() -> Vec<String> {
     match std::env::var("HACKERAI_ALLOWED_HOSTS") {
         Ok(hosts) => hosts.split(',').map(|s| s.trim().to_string()).collect(),
-        Err(_) => vec!["hackerai.co".to_string(), "localhost".to_string()],
+        Err(_) => {
+            let mut hosts = vec!["hackerai.co".to_string(), "localhost".to_string()];
+            // Trust the configured self-hosted origin without also requiring
+            // HACKERAI_ALLOWED_HOSTS to be set
+            if let Some(host) = url::Url::parse(&get_default_origin())
+                .ok()
+                .and_then(|parsed| parsed.host_str().map(str::to_string))
+            {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+            hosts
+        }
     }
 }
 
@@ -1396,6 +1530,39 @@ fn consume_pending_desktop_auth_state(app: &tauri::AppHandle, desktop_state: &st
         .unwrap_or(false)
 }
 
+#[derive(Clone, Serialize)]
+struct AuthErrorEvent {
+    reason: &'static str,
+}
+
+/// Notify the webview that an auth callback was rejected so it can leave the
+/// "waiting for browser" state instead of hanging until the user retries.
+fn emit_auth_error(app: &tauri::AppHandle, reason: &'static str) {
+    if let Err(e) = app.emit("auth-error", AuthErrorEvent { reason }) {
+        log::warn!("Failed to emit auth-error event: {}", e);
+    }
+}
+
+/// Render a deep link for logging without its query string, which carries the
+/// single-use auth handoff token.
+fn redact_deep_link(url: &url::Url) -> String {
+    let mut redacted = url.clone();
+    redacted.set_query(None);
+    redacted.set_fragment(None);
+    redacted.to_string()
+}
+
+/// Dispatch a `hackerai://` link: auth callbacks complete sign-in, anything
+/// else is routed to a view in the main window.
+fn handle_deep_link(app: &tauri::AppHandle, url: &url::Url) {
+    if let Some(route) = deep_link::parse(url) {
+        log::info!("Routing deep link to {} view", route.view());
+        deep_link::navigate(app, route);
+    } else {
+        handle_auth_deep_link(app, url);
+    }
+}
+
 fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
     if url.scheme() != "hackerai" {
         return;
@@ -1410,6 +1577,7 @@ fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
             Some(token) => {
                 if !is_valid_token_format(&token) {
                     log::error!("Invalid token format in deep link");
+                    emit_auth_error(app, "invalid_token");
                     return;
                 }
 
@@ -1421,6 +1589,7 @@ fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
                     Some(state) if consume_pending_desktop_auth_state(app, &state) => state,
                     _ => {
                         log::error!("Auth deep link missing valid desktop auth state");
+                        emit_auth_error(app, "invalid_state");
                         return;
                     }
                 };
@@ -1433,8 +1602,8 @@ fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
                         .map(|(_, v)| v.to_string())
                         .filter(|o| validate_origin(o))
                         .unwrap_or_else(|| {
-                            log::warn!("Deep link has missing or invalid origin, using production");
-                            "https://hackerai.co".to_string()
+                            log::warn!("Deep link has missing or invalid origin, using default");
+                            get_default_origin()
                         });
 
                     let encoded_token: String =
@@ -1445,10 +1614,7 @@ fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
                         "{}/desktop-callback?token={}&desktop_state={}",
                         origin, encoded_token, encoded_state
                     );
-                    log::info!(
-                        "Navigating to desktop callback (token: {}...)",
-                        &token[..8.min(token.len())]
-                    );
+                    log::info!("Navigating to desktop callback");
 
                     match callback_url.parse() {
                         Ok(parsed_url) => {
@@ -1471,17 +1637,26 @@ fn handle_auth_deep_link(app: &tauri::AppHandle, url: &url::Url) {
                 if let Some((_, error)) = url.query_pairs().find(|(k, _)| k == "error") {
                     log::error!("Auth deep link received with error: {}", error);
                 } else {
-                    log::warn!("Auth deep link received without token: {:?}", url);
+                    log::warn!(
+                        "Auth deep link received without token: {}",
+                        redact_deep_link(url)
+                    );
                 }
             }
         }
     }
 }
 
-async fn check_for_updates(app: tauri::AppHandle, silent: bool) {
+/// The updater configured in tauri.conf.json.
+fn configured_updater(app: &tauri::AppHandle) -> Result<tauri_plugin_updater::Updater, String> {
+    app.updater()
+        .map_err(|e| format!("Failed to configure updater: {}", e))
+}
+
+async fn run_update_check(app: tauri::AppHandle, silent: bool) {
     use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 
-    let updater = match app.updater() {
+    let updater = match configured_updater(&app) {
         Ok(updater) => updater,
         Err(e) => {
             if silent {
@@ -1517,11 +1692,23 @@ async fn check_for_updates(app: tauri::AppHandle, silent: bool) {
 
             if should_update {
                 log::info!("User accepted update to version {}", version);
-                if let Err(e) = update.download_and_install(|_, _| {}, || {}).await {
-                    log::error!("Failed to install update: {}", e);
+                let installed = match download_with_progress(&app, &update).await {
+                    Ok(bytes) => {
+                        // The Windows installer exits the app right away
+                        if cfg!(windows) {
+                            crash::mark_clean_exit();
+                        }
+                        update
+                            .install(bytes)
+                            .map_err(|e| format!("Failed to install update: {}", e))
+                    }
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = installed {
+                    log::error!("{}", e);
                     let _ = app
                         .dialog()
-                        .message(format!("Failed to install update: {}", e))
+                        .message(e)
                         .kind(MessageDialogKind::Error)
                         .title("Update Error")
                         .blocking_show();
@@ -1538,6 +1725,7 @@ async fn check_for_updates(app: tauri::AppHandle, silent: bool) {
                         ))
                         .blocking_show();
                     if restart_now {
+                        crash::mark_clean_exit();
                         app.restart();
                     }
                 }
@@ -1572,6 +1760,594 @@ async fn check_for_updates(app: tauri::AppHandle, silent: bool) {
     }
 }
 
+// ── Updater Commands ─────────────────────────────────────────────────
+
+/// Update found by the last `check_for_updates`, installed by `install_update`.
+struct PendingUpdate(std::sync::Mutex<Option<tauri_plugin_updater::Update>>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateInfo {
+    available: bool,
+    current_version: String,
+    version: Option<String>,
+    notes: Option<String>,
+    date: Option<String>,
+}
+
+/// Check for an update without prompting.
+#[tauri::command]
+async fn check_for_updates(
+    app: tauri::AppHandle,
+    pending_update: tauri::State<'_, PendingUpdate>,
+) -> Result<UpdateInfo, String> {
+    let update = configured_updater(&app)?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+    save_update_check_timestamp(&app);
+
+    let info = UpdateInfo {
+        available: update.is_some(),
+        current_version: app.package_info().version.to_string(),
+        version: update.as_ref().map(|u| u.version.clone()),
+        notes: update.as_ref().and_then(|u| u.body.clone()),
+        date: update
+            .as_ref()
+            .and_then(|u| u.date.map(|date| date.to_string())),
+    };
+    *pending_update
+        .0
+        .lock()
+        .map_err(|_| "update state lock poisoned".to_string())? = update;
+    Ok(info)
+}
+
+/// Update downloaded by `download_update` and waiting to be installed.
+struct StagedUpdate {
+    update: tauri_plugin_updater::Update,
+    bytes: Vec<u8>,
+    /// Install when the app exits instead of restarting right away.
+    install_on_exit: bool,
+}
+
+#[derive(Default)]
+struct StagedUpdateState(std::sync::Mutex<Option<StagedUpdate>>);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateProgressEvent {
+    version: String,
+    downloaded: u64,
+    content_length: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateStagedEvent {
+    version: String,
+}
+
+/// Download `update`, emitting `update-download-progress` as chunks arrive.
+async fn download_with_progress(
+    app: &tauri::AppHandle,
+    update: &tauri_plugin_updater::Update,
+) -> Result<Vec<u8>, String> {
+    let mut downloaded: u64 = 0;
+    update
+        .download(
+            |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                let _ = app.emit(
+                    "update-download-progress",
+                    UpdateProgressEvent {
+                        version: update.version.clone(),
+                        downloaded,
+                        content_length,
+                    },
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to download update: {}", e))
+}
+
+/// Install a staged update if the user chose to apply it on next launch.
+fn install_staged_update_on_exit(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<StagedUpdateState>() else {
+        return;
+    };
+    let staged = match state.0.lock() {
+        Ok(mut staged) if staged.as_ref().is_some_and(|s| s.install_on_exit) => staged.take(),
+        _ => None,
+    };
+    if let Some(staged) = staged {
+        log::info!("Installing update {} on exit", staged.update.version);
+        if let Err(e) = staged.update.install(&staged.bytes) {
+            log::error!("Failed to install update on exit: {}", e);
+        }
+    }
+}
+
+/// Download the update found by `check_for_updates` (checking again if there
+/// is none) without installing it. Progress is reported through
+/// `update-download-progress`; `update-staged` fires once it is ready.
+#[tauri::command]
+async fn download_update(
+    app: tauri::AppHandle,
+    pending_update: tauri::State<'_, PendingUpdate>,
+    staged_update: tauri::State<'_, StagedUpdateState>,
+) -> Result<String, String> {
+    let pending = pending_update
+        .0
+        .lock()
+        .map_err(|_| "update state lock poisoned".to_string())?
+        .take();
+    let update = match pending {
+        Some(update) => update,
+        None => configured_updater(&app)?
+            .check()
+            .await
+            .map_err(|e| format!("Failed to check for updates: {}", e))?
+            .ok_or("No update available")?,
+    };
+
+    log::info!("Downloading update {}", update.version);
+    let bytes = download_with_progress(&app, &update).await?;
+    let version = update.version.clone();
+    *staged_update
+        .0
+        .lock()
+        .map_err(|_| "update state lock poisoned".to_string())? = Some(StagedUpdate {
+        update,
+        bytes,
+        install_on_exit: false,
+    });
+
+    let _ = app.emit(
+        "update-staged",
+        UpdateStagedEvent {
+            version: version.clone(),
+        },
+    );
+    let in_front = app
+        .get_webview_window("main")
+        .map(|w| w.is_visible().unwrap_or(false) && w.is_focused().unwrap_or(false))
+        .unwrap_or(false);
+    if !in_front {
+        use tauri_plugin_notification::NotificationExt;
+        let _ = app
+            .notification()
+            .builder()
+            .title("Update ready")
+            .body(format!(
+                "HackerAI {} has been downloaded and is ready to install.",
+                version
+            ))
+            .show();
+    }
+    Ok(version)
+}
+
+/// Apply the update staged by `download_update`. With `restart`, install now
+/// and relaunch into the new version; otherwise install when the app next
+/// exits so a running session isn't interrupted.
+#[tauri::command]
+fn install_update(
+    app: tauri::AppHandle,
+    staged_update: tauri::State<'_, StagedUpdateState>,
+    restart: bool,
+) -> Result<(), String> {
+    let mut staged = staged_update
+        .0
+        .lock()
+        .map_err(|_| "update state lock poisoned".to_string())?;
+    if !restart {
+        let staged = staged.as_mut().ok_or("No update has been downloaded")?;
+        log::info!(
+            "Update {} will be installed on next launch",
+            staged.update.version
+        );
+        staged.install_on_exit = true;
+        return Ok(());
+    }
+
+    let staged = staged.take().ok_or("No update has been downloaded")?;
+    log::info!("Installing update {}", staged.update.version);
+    crash::mark_clean_exit();
+    staged
+        .update
+        .install(&staged.bytes)
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+    app.restart()
+}
+
+// ── Local Data ───────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalWipeReport {
+    browsing_data_cleared: bool,
+    removed: Vec<String>,
+    failed: Vec<String>,
+}
+
+/// Staging directory the web app uses for files uploaded to the local sandbox.
+fn upload_staging_dir() -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from("C:\\temp\\hackerai-upload")
+    } else {
+        PathBuf::from("/tmp/hackerai-upload")
+    }
+}
+
+/// Sign out and remove everything the app keeps on this machine: the webview
+/// session (cookies, storage, cache), app data/cache/log directories, and
+/// staged uploads. Running local commands and terminals are stopped first so
+/// nothing keeps writing into the removed directories.
+#[tauri::command]
+async fn logout_and_wipe(app: tauri::AppHandle) -> Result<LocalWipeReport, String> {
+    if let Some(pty_state) = app.try_state::<PtyState>() {
+        if let Ok(mut manager) = pty_state.lock() {
+            manager.stop_all();
+        }
+    }
+    if let Some(stream_state) = app.try_state::<StreamCommandState>() {
+        cancel_all_stream_commands(&stream_state).await;
+    }
+    if let Some(pending_states) = app.try_state::<PendingDesktopAuthStates>() {
+        if let Ok(mut states) = pending_states.0.lock() {
+            states.clear();
+        }
+    }
+
+    let mut browsing_data_cleared = true;
+    for (label, window) in app.webview_windows() {
+        if let Err(e) = window.clear_all_browsing_data() {
+            log::error!("Failed to clear browsing data for '{}': {}", label, e);
+            browsing_data_cleared = false;
+        }
+    }
+
+    let mut dirs: Vec<PathBuf> = [
+        app.path().app_data_dir(),
+        app.path().app_local_data_dir(),
+        app.path().app_cache_dir(),
+        app.path().app_log_dir(),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect();
+    dirs.push(upload_staging_dir());
+    dirs.sort();
+    dirs.dedup();
+
+    // Close the log file and drop the session marker, both in the log dir
+    crash::mark_clean_exit();
+    logging::detach();
+
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+    for dir in dirs {
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => removed.push(dir.display().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                log::warn!("Failed to remove {}: {}", dir.display(), e);
+                failed.push(format!("{}: {}", dir.display(), e));
+            }
+        }
+    }
+
+    log::info!(
+        "Local wipe finished ({} removed, {} failed)",
+        removed.len(),
+        failed.len()
+    );
+    Ok(LocalWipeReport {
+        browsing_data_cleared,
+        removed,
+        failed,
+    })
+}
+
+// ── Server Origin Commands ───────────────────────────────────────────
+
+/// Web app origin for this session, used by the offline page.
+#[tauri::command]
+fn get_app_origin() -> String {
+    get_default_origin()
+}
+
+// ── Deep Link Commands ───────────────────────────────────────────────
+
+/// Route from a deep link the webview hasn't handled yet. Call on load and
+/// after handling `deep-link-navigate` so a reload doesn't replay it.
+#[tauri::command]
+fn take_pending_deep_link(app: tauri::AppHandle) -> Option<deep_link::DeepLinkRoute> {
+    deep_link::take_pending(&app)
+}
+
+// ── Download Commands ────────────────────────────────────────────────
+
+/// Download an agent artifact into the downloads directory, emitting
+/// `download-progress`, and verify it against the expected size / SHA-256.
+#[tauri::command]
+async fn download_file(
+    app: tauri::AppHandle,
+    url: String,
+    file_name: Option<String>,
+    expected_size: Option<u64>,
+    sha256: Option<String>,
+) -> Result<downloads::DownloadResult, String> {
+    downloads::download(&app, &url, file_name, expected_size, sha256).await
+}
+
+#[tauri::command]
+fn get_download_dir(app: tauri::AppHandle) -> Result<String, String> {
+    downloads::download_dir(&app).map(|dir| dir.display().to_string())
+}
+
+#[tauri::command]
+fn set_download_dir(app: tauri::AppHandle, dir: Option<String>) -> Result<String, String> {
+    downloads::set_download_dir(&app, dir)
+}
+
+#[tauri::command]
+fn open_download(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    downloads::open(&app, &path)
+}
+
+#[tauri::command]
+fn reveal_download(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    downloads::reveal(&app, &path)
+}
+
+/// Cancel a `download_file` in flight. Returns whether it was still running.
+#[tauri::command]
+fn cancel_download(app: tauri::AppHandle, id: String) -> Result<bool, String> {
+    downloads::cancel(&app, &id)
+}
+
+// ── Evidence Commands ────────────────────────────────────────────────
+
+/// Capture the screen, a window, or a region into the evidence directory.
+/// Returns `null` if the user cancels an interactive selection.
+#[tauri::command]
+async fn capture_screenshot(
+    app: tauri::AppHandle,
+    mode: screenshot::CaptureMode,
+    region: Option<screenshot::Region>,
+    hide_app: Option<bool>,
+) -> Result<Option<screenshot::ScreenshotResult>, String> {
+    screenshot::capture(&app, mode, region, hide_app.unwrap_or(true)).await
+}
+
+#[tauri::command]
+fn get_evidence_dir(app: tauri::AppHandle) -> Result<String, String> {
+    screenshot::evidence_dir(&app).map(|dir| dir.display().to_string())
+}
+
+#[tauri::command]
+fn set_evidence_dir(app: tauri::AppHandle, dir: Option<String>) -> Result<String, String> {
+    screenshot::set_evidence_dir(&app, dir)
+}
+
+// ── Network Commands ─────────────────────────────────────────────────
+
+/// Last connectivity result, or `null` before the first probe finishes.
+#[tauri::command]
+fn get_network_status(app: tauri::AppHandle) -> Option<network::NetworkStatus> {
+    network::current(&app)
+}
+
+/// Probe the backend now, e.g. when the user clicks "Retry".
+#[tauri::command]
+async fn check_network(app: tauri::AppHandle) -> Result<network::NetworkStatus, String> {
+    network::check(&app).await
+}
+
+// ── Crash Report Commands ────────────────────────────────────────────
+
+#[tauri::command]
+fn list_crash_reports() -> Result<Vec<crash::CrashReportSummary>, String> {
+    crash::list()
+}
+
+/// Full report, so the user can review exactly what would be sent.
+#[tauri::command]
+fn get_crash_report(id: String) -> Result<crash::CrashReport, String> {
+    crash::get(&id)
+}
+
+#[tauri::command]
+fn delete_crash_report(id: String) -> Result<(), String> {
+    crash::delete(&id)
+}
+
+/// Send a report to HackerAI. `consent` must be true, and only set after the
+/// user has reviewed the report and agreed to send it.
+#[tauri::command]
+async fn upload_crash_report(
+    app: tauri::AppHandle,
+    id: String,
+    consent: bool,
+) -> Result<crash::CrashReport, String> {
+    crash::upload(&app, &id, consent).await
+}
+
+// ── Window Commands ──────────────────────────────────────────────────
+
+/// Open an additional window at a chat route so an agent run can stay visible
+/// while working in another chat. Returns the new window's label.
+#[tauri::command]
+async fn open_chat_window(app: tauri::AppHandle, path: Option<String>) -> Result<String, String> {
+    chat_window::open(&app, path.as_deref())
+}
+
+/// Export the calling window's current view (e.g. a chat) as a PDF.
+#[tauri::command]
+async fn print_to_pdf(
+    window: tauri::WebviewWindow,
+    file_name: Option<String>,
+) -> Result<print::PrintResult, String> {
+    print::print_to_pdf(&window, file_name).await
+}
+
+/// Show or hide the always-on-top mini window. Returns whether it is visible.
+#[tauri::command]
+async fn toggle_mini_mode(app: tauri::AppHandle) -> Result<bool, String> {
+    mini_window::toggle(&app)
+}
+
+/// Push the current agent progress to the mini window.
+#[tauri::command]
+fn update_mini_status(
+    app: tauri::AppHandle,
+    status: mini_window::MiniStatus,
+) -> Result<(), String> {
+    mini_window::update(&app, status)
+}
+
+#[tauri::command]
+fn get_mini_status(app: tauri::AppHandle) -> mini_window::MiniStatus {
+    mini_window::current(&app)
+}
+
+#[tauri::command]
+fn show_main_window(app: tauri::AppHandle) {
+    tray::show_main_window(&app);
+}
+
+// ── Notification Commands ────────────────────────────────────────────
+
+/// Called by the webview when an agent run finishes, fails, or needs approval.
+/// Shows a native notification only if the calling window isn't in front.
+#[tauri::command]
+fn notify_agent_event(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    kind: notifications::AgentEventKind,
+    chat_title: Option<String>,
+    chat_id: Option<String>,
+) -> Result<bool, String> {
+    notifications::notify_agent_event(&app, window.label(), kind, chat_title, chat_id)
+}
+
+/// Set the dock / taskbar badge for unread results or pending approvals.
+/// A count of 0 clears it.
+#[tauri::command]
+fn set_badge_count(app: tauri::AppHandle, count: u32) -> Result<(), String> {
+    badge::set(&app, count)
+}
+
+#[tauri::command]
+fn clear_badge(app: tauri::AppHandle) -> Result<(), String> {
+    badge::set(&app, 0)
+}
+
+// ── Autostart Commands ───────────────────────────────────────────────
+
+#[tauri::command]
+fn get_autostart(app: tauri::AppHandle) -> Result<autostart::AutostartInfo, String> {
+    autostart::info(&app)
+}
+
+/// Launch the app at login so the local command server is ready without
+/// opening it manually. `start_minimized` keeps it in the tray when launched
+/// that way.
+#[tauri::command]
+fn set_autostart(
+    app: tauri::AppHandle,
+    enabled: bool,
+    start_minimized: Option<bool>,
+) -> Result<autostart::AutostartInfo, String> {
+    autostart::set(&app, enabled, start_minimized)
+}
+
+// ── Power Commands ───────────────────────────────────────────────────
+
+/// Keep the machine awake (display may still sleep) while an agent run or
+/// local task is active. Returns an id for `release_wake_lock`.
+#[tauri::command]
+fn acquire_wake_lock(
+    state: tauri::State<'_, power::WakeLockState>,
+    reason: Option<String>,
+) -> Result<String, String> {
+    power::acquire(&state, reason.unwrap_or_else(|| "agent run".to_string()))
+}
+
+#[tauri::command]
+fn release_wake_lock(
+    state: tauri::State<'_, power::WakeLockState>,
+    id: String,
+) -> Result<bool, String> {
+    power::release(&state, &id)
+}
+
+// ── Theme Commands ───────────────────────────────────────────────────
+
+/// Current theme preference, the theme windows are rendered with, and the OS
+/// dark/light preference if known.
+#[tauri::command]
+async fn get_theme(app: tauri::AppHandle) -> theme::ThemeInfo {
+    theme::info(&app)
+}
+
+/// Follow the OS appearance (`system`) or force `dark` / `light` for every
+/// window. Emits `theme-changed`.
+#[tauri::command]
+async fn set_theme(
+    app: tauri::AppHandle,
+    preference: settings::ThemePreference,
+) -> Result<theme::ThemeInfo, String> {
+    theme::set(&app, preference)
+}
+
+// ── Zoom Commands ────────────────────────────────────────────────────
+
+#[tauri::command]
+fn get_zoom_level(app: tauri::AppHandle) -> f64 {
+    zoom::current(&app)
+}
+
+/// Set the webview zoom factor for all windows and remember it across launches.
+#[tauri::command]
+fn set_zoom_level(app: tauri::AppHandle, level: f64) -> Result<f64, String> {
+    zoom::set(&app, level)
+}
+
+// ── Global Shortcut Commands ─────────────────────────────────────────
+
+#[tauri::command]
+fn get_global_shortcut(app: tauri::AppHandle) -> Option<String> {
+    settings::load(&app).global_shortcut
+}
+
+/// Change the show/hide hotkey. Pass `null` to disable it.
+#[tauri::command]
+fn set_global_shortcut(
+    app: tauri::AppHandle,
+    shortcut: Option<String>,
+) -> Result<Option<String>, String> {
+    shortcut::set(&app, shortcut)
+}
+
+#[tauri::command]
+fn get_mini_mode_shortcut(app: tauri::AppHandle) -> Option<String> {
+    settings::load(&app).mini_mode_shortcut
+}
+
+/// Change the mini mode hotkey. Pass `null` to disable it.
+#[tauri::command]
+fn set_mini_mode_shortcut(
+    app: tauri::AppHandle,
+    shortcut: Option<String>,
+) -> Result<Option<String>, String> {
+    shortcut::set_mini_mode(&app, shortcut)
+}
+
 // ── PTY Commands ─────────────────────────────────────────────────────
 
 type PtyState = std::sync::Arc<std::sync::Mutex<pty::PtyManager>>;
@@ -1621,6 +2397,12 @@ async fn execute_pty_kill(
     manager.kill(&session_id)
 }
 
+/// Install the logger. Records also go to the app log directory once
+/// `run()` has resolved it.
+pub fn init_logging() {
+    logging::init();
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -1630,6 +2412,48 @@ pub fn run() {
             get_cmd_server_info,
             get_local_file_metadata,
             read_local_file,
+            logout_and_wipe,
+            check_for_updates,
+            download_update,
+            install_update,
+            take_pending_deep_link,
+            get_app_origin,
+            download_file,
+            get_download_dir,
+            set_download_dir,
+            open_download,
+            reveal_download,
+            cancel_download,
+            capture_screenshot,
+            get_evidence_dir,
+            set_evidence_dir,
+            get_network_status,
+            check_network,
+            list_crash_reports,
+            get_crash_report,
+            delete_crash_report,
+            upload_crash_report,
+            open_chat_window,
+            print_to_pdf,
+            toggle_mini_mode,
+            update_mini_status,
+            get_mini_status,
+            show_main_window,
+            notify_agent_event,
+            set_badge_count,
+            clear_badge,
+            get_autostart,
+            set_autostart,
+            acquire_wake_lock,
+            release_wake_lock,
+            get_theme,
+            set_theme,
+            get_zoom_level,
+            set_zoom_level,
+            get_global_shortcut,
+            set_global_shortcut,
+            get_mini_mode_shortcut,
+            set_mini_mode_shortcut,
             execute_command,
             execute_stream_command,
             cancel_stream_command,
@@ -1645,31 +2469,113 @@ pub fn run() {
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(shortcut::plugin())
+        .plugin(autostart::plugin())
+        .plugin(
+            // Main window geometry, maximized and fullscreen only: restoring
+            // VISIBLE would relaunch the app hidden after quitting from the
+            // tray, and chat windows get fresh labels every time. The plugin
+            // moves windows back on screen if their monitor is gone.
+            tauri_plugin_window_state::Builder::default()
+                .with_filter(|label| label == "main")
+                .with_state_flags(
+                    tauri_plugin_window_state::StateFlags::SIZE
+                        | tauri_plugin_window_state::StateFlags::POSITION
+                        | tauri_plugin_window_state::StateFlags::MAXIMIZED
+                        | tauri_plugin_window_state::StateFlags::FULLSCREEN,
+                )
+                .build(),
+        )
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
             // Handle deep links passed as CLI args (Linux/Windows)
-            log::info!("Single instance callback with args: {:?}", args);
+            log::info!(
+                "Single instance callback with {} args",
+                args.len().saturating_sub(1)
+            );
             for arg in args.iter().skip(1) {
                 if let Ok(url) = url::Url::parse(arg) {
                     if url.scheme() == "hackerai" {
-                        log::info!("Processing deep link from CLI arg: {}", arg);
-                        handle_auth_deep_link(app, &url);
+                        log::info!(
+                            "Processing deep link from CLI arg: {}",
+                            redact_deep_link(&url)
+                        );
+                        handle_deep_link(app, &url);
                     }
                 }
             }
-            // Focus the main window
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.set_focus();
-            }
+            // Bring back the main window, which may be hidden to the tray
+            tray::show_main_window(app);
         }))
+        .on_menu_event(|app, event| menu::handle_event(app, event))
+        .on_window_event(|window, event| match event {
+            // Keep running in the tray instead of quitting when the main
+            // window is closed
+            tauri::WindowEvent::CloseRequested { api, .. } => {
+                if window.label() == "main" && tray::is_active(window.app_handle()) {
+                    let _ = window.hide();
+                    api.prevent_close();
+                }
+            }
+            tauri::WindowEvent::Focused(true) => {
+                notifications::on_window_focused(window.app_handle(), window.label());
+            }
+            tauri::WindowEvent::ThemeChanged(new_theme) if window.label() == "main" => {
+                theme::on_theme_changed(window.app_handle(), *new_theme);
+            }
+            _ => {}
+        })
         .manage(std::sync::Arc::new(std::sync::Mutex::new(pty::PtyManager::new())) as PtyState)
         .manage(
             std::sync::Arc::new(std::sync::Mutex::new(HashMap::<String, u32>::new()))
                 as StreamCommandState,
         )
+        .manage(notifications::PendingNotificationTarget::default())
+        .manage(deep_link::PendingDeepLink::default())
+        .manage(power::WakeLockState::default())
+        .manage(mini_window::MiniStatusState::default())
+        .manage(downloads::DownloadState::default())
+        .manage(network::NetworkMonitor::default())
+        .manage(PendingUpdate(std::sync::Mutex::new(None)))
+        .manage(StagedUpdateState::default())
         .manage(PendingDesktopAuthStates(std::sync::Mutex::new(
             HashMap::new(),
         )))
         .setup(|app| {
+            match app.path().app_log_dir() {
+                Ok(dir) => {
+                    if let Err(e) = logging::attach_file(&dir) {
+                        log::warn!("Failed to open log file: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("Log directory is unavailable: {}", e),
+            }
+            crash::install(app.handle());
+            init_default_origin(app.handle());
+            app.manage(http::HttpClient::new(app.handle())?);
+            // The main window is created here instead of from tauri.conf.json
+            // so its downloads can be intercepted
+            let main_config = app
+                .config()
+                .app
+                .windows
+                .iter()
+                .find(|window| window.label == "main")
+                .cloned()
+                .ok_or("Main window config is missing")?;
+            tauri::WebviewWindowBuilder::from_config(app.handle(), &main_config)?
+                .on_download(downloads::handle_webview_download)
+                .build()?;
+            let app_menu = menu::build(app.handle())?;
+            app.set_menu(app_menu)?;
+            zoom::restore(app.handle());
+            theme::restore(app.handle());
+            if let Err(e) = tray::init(app.handle()) {
+                log::warn!("Failed to create tray icon: {}", e);
+            }
+            shortcut::init(app.handle());
+            autostart::apply_launch_state(app.handle());
+
             #[cfg(desktop)]
             {
                 use tauri_plugin_deep_link::DeepLinkExt;
@@ -1688,30 +2594,45 @@ pub fn run() {
                 let handle = app.handle().clone();
                 app.deep_link().on_open_url(move |event| {
                     let urls = event.urls();
-                    log::info!("Deep link received: {:?}", urls);
+                    log::info!(
+                        "Deep link received: {:?}",
+                        urls.iter().map(redact_deep_link).collect::<Vec<_>>()
+                    );
 
                     for url in urls {
-                        handle_auth_deep_link(&handle, &url);
+                        handle_deep_link(&handle, &url);
                     }
                 });
+
+                // A link that launched the app on Linux/Windows arrives as a
+                // CLI arg rather than through on_open_url
+                #[cfg(any(target_os = "linux", target_os = "windows"))]
+                {
+                    if let Ok(Some(urls)) = app.deep_link().get_current() {
+                        for url in urls {
+                            handle_deep_link(app.handle(), &url);
+                        }
+                    }
+                }
             }
-            // Start dev auth callback server when running in debug mode
-            // (deep links don't work with `tauri dev` on macOS)
-            #[cfg(debug_assertions)]
-            {
-                let dev_handle = app.handle().clone();
-                tauri::async_runtime::spawn(start_dev_auth_server(dev_handle));
+            // Start the loopback auth callback server in debug mode (deep
+            // links don't work with `tauri dev` on macOS) or when opted in
+            if cfg!(debug_assertions) || loopback_auth_enabled() {
+                let loopback_handle = app.handle().clone();
+                tauri::async_runtime::spawn(start_loopback_auth_server(loopback_handle));
             }
 
             // Start command execution server (always, for local terminal commands)
             tauri::async_runtime::spawn(start_cmd_server());
 
+            network::start(app.handle().clone());
+
             // Check for updates on every launch
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 log::info!("Running update check on launch");
                 save_update_check_timestamp(&handle);
-                check_for_updates(handle.clone(), true).await;
+                run_update_check(handle.clone(), true).await;
 
                 // Then check every hour if 24h has passed (for long-running sessions)
                 loop {
@@ -1719,7 +2640,7 @@ pub fn run() {
                     if should_check_for_updates(&handle) {
                         log::info!("Running scheduled update check (24h interval)");
                         save_update_check_timestamp(&handle);
-                        check_for_updates(handle.clone(), true).await;
+                        run_update_check(handle.clone(), true).await;
                     }
                 }
             });
@@ -1729,13 +2650,96 @@ pub fn run() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|app, event| {
-            if let tauri::RunEvent::Exit = event {
+        .run(|app, event| match event {
+            tauri::RunEvent::Exit => {
                 if let Some(pty_state) = app.try_state::<PtyState>() {
                     if let Ok(mut manager) = pty_state.lock() {
                         manager.stop_all();
                     }
                 }
+                // Streamed commands run in their own process group and outlive
+                // the app unless we tear them down explicitly.
+                if let Some(stream_state) = app.try_state::<StreamCommandState>() {
+                    tauri::async_runtime::block_on(cancel_all_stream_commands(&stream_state));
+                }
+                if let Some(wake_locks) = app.try_state::<power::WakeLockState>() {
+                    power::release_all(&wake_locks);
+                }
+                crash::mark_clean_exit();
+                install_staged_update_on_exit(app);
             }
+            // Clicking the dock icon brings back a window hidden to the tray
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Reopen {
+                has_visible_windows: false,
+                ..
+            } => tray::show_main_window(app),
+            _ => {}
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_origin_keeps_scheme_host_and_port() {
+        assert_eq!(
+            normalize_origin("https://hackerai.example.com/chat?x=1#top"),
+            Some("https://hackerai.example.com".to_string())
+        );
+        assert_eq!(
+            normalize_origin("https://hackerai.example.com:8443/"),
+            Some("https://hackerai.example.com:8443".to_string())
+        );
+        assert_eq!(
+            normalize_origin("  https://hackerai.example.com/ \n"),
+            Some("https://hackerai.example.com".to_string())
+        );
+        assert_eq!(
+            normalize_origin("http://localhost:3000/path"),
+            Some("http://localhost:3000".to_string())
+        );
+    }
+
+    #[test]
+    fn normalize_origin_rejects_insecure_and_invalid_values() {
+        assert_eq!(normalize_origin("http://hackerai.example.com"), None);
+        assert_eq!(normalize_origin("http://127.0.0.1:3000"), None);
+        assert_eq!(normalize_origin("ftp://hackerai.example.com"), None);
+        assert_eq!(normalize_origin("hackerai://auth"), None);
+        assert_eq!(normalize_origin("hackerai.example.com"), None);
+        assert_eq!(normalize_origin(""), None);
+    }
+
+    #[test]
+    fn resolve_origin_prefers_env_then_settings() {
+        assert_eq!(
+            resolve_origin(
+                Some("https://env.example.com"),
+                Some("https://settings.example.com")
+            ),
+            "https://env.example.com"
+        );
+        assert_eq!(
+            resolve_origin(None, Some("https://settings.example.com/")),
+            "https://settings.example.com"
+        );
+        assert_eq!(resolve_origin(None, None), DEFAULT_ORIGIN);
+    }
+
+    #[test]
+    fn resolve_origin_skips_invalid_values() {
+        assert_eq!(
+            resolve_origin(
+                Some("http://env.example.com"),
+                Some("https://settings.example.com")
+            ),
+            "https://settings.example.com"
+        );
+        assert_eq!(
+            resolve_origin(Some("not a url"), Some("http://settings.example.com")),
+            DEFAULT_ORIGIN
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const LOG_FILE: &str = "hackerai.log";
+/// Start a fresh file on launch once the previous one grows past this.
+const MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Log file in the app log directory, attached once the app has resolved its
+/// paths. Until then records only go to stderr.
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Writes every record to stderr and, once attached, to the log file.
+struct Tee;
+
+impl Write for Tee {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut file) = FILE.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.write_all(buf);
+            }
+        }
+        io::stderr().write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Ok(mut file) = FILE.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.flush();
+            }
+        }
+        io::stderr().flush()
+    }
+}
+
+/// Install the global logger (`RUST_LOG` filter, `info` by default).
+pub fn init() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .target(env_logger::Target::Pipe(Box::new(Tee)))
+        .init();
+}
+
+/// Start mirroring log records into `dir`. Returns the log file path.
+pub fn attach_file(dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(LOG_FILE);
+    let too_large = fs::metadata(&path)
+        .map(|metadata| metadata.len() > MAX_LOG_SIZE)
+        .unwrap_or(false);
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!too_large)
+        .truncate(too_large)
+        .open(&path)?;
+    if let Ok(mut current) = FILE.lock() {
+        *current = Some(file);
+    }
+    Ok(path)
+}
+
+/// Stop mirroring into the log file and close it, so the log directory can be
+/// removed (Windows won't delete an open file). Records go to stderr only
+/// until the next launch.
+pub fn detach() {
+    if let Ok(mut current) = FILE.lock() {
+        if let Some(mut file) = current.take() {
+            let _ = file.flush();
+        }
+    }
+}
@@ -6,7 +6,7 @@
 
 fn main() {
     // Initialize logging
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    hackerai_desktop_lib::init_logging();
 
     hackerai_desktop_lib::run()
 }
@@ -0,0 +1,216 @@
+use serde::Serialize;
+use tauri::menu::{
+    Menu, MenuBuilder, MenuEvent, MenuItem, MenuItemBuilder, SubmenuBuilder, HELP_SUBMENU_ID,
+    WINDOW_SUBMENU_ID,
+};
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow, Wry};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{chat_window, mini_window, zoom};
+
+const NEW_CHAT: &str = "new_chat";
+const NEW_WINDOW: &str = "new_window";
+const GO_BACK: &str = "go_back";
+const GO_FORWARD: &str = "go_forward";
+const RELOAD: &str = "reload";
+const ZOOM_IN: &str = "zoom_in";
+const ZOOM_OUT: &str = "zoom_out";
+const ZOOM_RESET: &str = "zoom_reset";
+const MINI_MODE: &str = "mini_mode";
+const CHECK_UPDATES: &str = "check_updates";
+const DIAGNOSTICS: &str = "diagnostics";
+const OPEN_LOGS: &str = "open_logs";
+
+/// Payload of `menu-action`, emitted for items the web app has to handle.
+#[derive(Clone, Serialize)]
+struct MenuActionEvent {
+    action: &'static str,
+}
+
+fn item(app: &AppHandle, id: &str, text: &str, accelerator: &str) -> tauri::Result<MenuItem<Wry>> {
+    MenuItemBuilder::with_id(id, text)
+        .accelerator(accelerator)
+        .build(app)
+}
+
+/// Build the application menu: File, Edit, View, Window, and Help, plus the
+/// standard app menu on macOS. The predefined Edit items are what make
+/// Cmd+C/V and friends work inside the webview on macOS.
+///
+/// On Windows/Linux the menu is attached to each window; the mini window
+/// removes it again.
+pub fn build(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let file = SubmenuBuilder::new(app, "File")
+        .item(&item(app, NEW_CHAT, "New Chat", "CmdOrCtrl+N")?)
+        .item(&item(app, NEW_WINDOW, "New Window", "CmdOrCtrl+Shift+N")?)
+        .separator()
+        .close_window()
+        .build()?;
+
+    let edit = SubmenuBuilder::new(app, "Edit")
+        .undo()
+        .redo()
+        .separator()
+        .cut()
+        .copy()
+        .paste()
+        .select_all()
+        .build()?;
+
+    let view = SubmenuBuilder::new(app, "View")
+        .item(&item(app, RELOAD, "Reload", "CmdOrCtrl+R")?)
+        .separator()
+        .item(&item(app, GO_BACK, "Go Back", "CmdOrCtrl+[")?)
+        .item(&item(app, GO_FORWARD, "Go Forward", "CmdOrCtrl+]")?)
+        .separator()
+        .item(&item(app, ZOOM_IN, "Zoom In", "CmdOrCtrl+=")?)
+        .item(&item(app, ZOOM_OUT, "Zoom Out", "CmdOrCtrl+-")?)
+        .item(&item(app, ZOOM_RESET, "Actual Size", "CmdOrCtrl+0")?)
+        .separator()
+        .fullscreen()
+        .build()?;
+
+    // The Window and Help ids let macOS add its window list and help search
+    let window = SubmenuBuilder::with_id(app, WINDOW_SUBMENU_ID, "Window")
+        .minimize()
+        .maximize()
+        .separator()
+        .item(&MenuItemBuilder::with_id(MINI_MODE, "Mini Mode").build(app)?)
+        .build()?;
+
+    let help = SubmenuBuilder::with_id(app, HELP_SUBMENU_ID, "Help")
+        .item(&MenuItemBuilder::with_id(CHECK_UPDATES, "Check for Updates…").build(app)?)
+        .separator()
+        .item(&MenuItemBuilder::with_id(DIAGNOSTICS, "Diagnostics").build(app)?)
+        .item(&MenuItemBuilder::with_id(OPEN_LOGS, "Open Logs Folder").build(app)?)
+        .build()?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let app_menu = SubmenuBuilder::new(app, "HackerAI")
+            .about(None)
+            .separator()
+            .services()
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .separator()
+            .quit()
+            .build()?;
+        MenuBuilder::new(app)
+            .items(&[&app_menu, &file, &edit, &view, &window, &help])
+            .build()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        MenuBuilder::new(app)
+            .items(&[&file, &edit, &view, &window, &help])
+            .build()
+    }
+}
+
+/// Route a menu click to the window the user is looking at.
+pub fn handle_event(app: &AppHandle, event: MenuEvent) {
+    let zoom_result = match event.id().as_ref() {
+        ZOOM_IN => Some(zoom::step(app, 1)),
+        ZOOM_OUT => Some(zoom::step(app, -1)),
+        ZOOM_RESET => Some(zoom::set(app, 1.0)),
+        _ => None,
+    };
+    if let Some(result) = zoom_result {
+        if let Err(e) = result {
+            log::warn!("Failed to persist zoom level: {}", e);
+        }
+        return;
+    }
+
+    let app_result = match event.id().as_ref() {
+        NEW_WINDOW => Some(chat_window::open(app, None).map(|_| ())),
+        MINI_MODE => Some(mini_window::toggle(app).map(|_| ())),
+        OPEN_LOGS => Some(open_logs_folder(app)),
+        DIAGNOSTICS => {
+            show_diagnostics(app);
+            Some(Ok(()))
+        }
+        CHECK_UPDATES => {
+            tauri::async_runtime::spawn(crate::run_update_check(app.clone(), false));
+            Some(Ok(()))
+        }
+        _ => None,
+    };
+    if let Some(result) = app_result {
+        if let Err(e) = result {
+            log::warn!("Menu action '{}' failed: {}", event.id().as_ref(), e);
+        }
+        return;
+    }
+
+    let Some(window) = target_window(app) else {
+        return;
+    };
+
+    let result = match event.id().as_ref() {
+        NEW_CHAT => window.emit_to(
+            window.label(),
+            "menu-action",
+            MenuActionEvent { action: "new-chat" },
+        ),
+        GO_BACK => window.eval("window.history.back()"),
+        GO_FORWARD => window.eval("window.history.forward()"),
+        RELOAD => window.reload(),
+        _ => return,
+    };
+    if let Err(e) = result {
+        log::warn!("Menu action '{}' failed: {}", event.id().as_ref(), e);
+    }
+}
+
+/// The focused webview window, falling back to the main window when the menu
+/// is used while no window has focus (macOS menu bar).
+fn target_window(app: &AppHandle) -> Option<WebviewWindow> {
+    app.webview_windows()
+        .into_values()
+        .find(|window| window.is_focused().unwrap_or(false))
+        .or_else(|| app.get_webview_window("main"))
+}
+
+fn open_logs_folder(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Log directory is unavailable: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Mkdir error: {}", e))?;
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open log directory: {}", e))
+}
+
+/// Version and environment details to paste into bug reports.
+fn show_diagnostics(app: &AppHandle) {
+    let package = app.package_info();
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|_| "unavailable".to_string());
+    let details = format!(
+        "{} {}\nOS: {} {} ({})\nTauri: {}\nWebview: {}\nLogs: {}",
+        package.name,
+        package.version,
+        std::env::consts::OS,
+        tauri_plugin_os::version(),
+        std::env::consts::ARCH,
+        tauri::VERSION,
+        tauri::webview_version().unwrap_or_else(|_| "unknown".to_string()),
+        log_dir,
+    );
+
+    app.dialog()
+        .message(details)
+        .title("Diagnostics")
+        .kind(MessageDialogKind::Info)
+        .show(|_| {});
+}
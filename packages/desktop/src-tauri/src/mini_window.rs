@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::theme;
+
+/// Must stay in sync with `capabilities/mini.json`.
+pub const LABEL: &str = "mini";
+
+/// Agent progress shown in the mini window, pushed by the main webview.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MiniStatus {
+    /// Short state label, e.g. "running", "waiting for approval", "done".
+    pub status: Option<String>,
+    pub chat_title: Option<String>,
+    pub last_message: Option<String>,
+    /// Completion in 0.0–1.0 when known.
+    pub progress: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct MiniStatusState(Mutex<MiniStatus>);
+
+/// Show the mini window, creating it on first use, or hide it if visible.
+/// Returns whether it is now visible.
+///
+/// The window loads the bundled `mini.html`. Under `tauri dev` app URLs
+/// resolve against `devUrl` (the Next.js server), which doesn't serve it, so
+/// mini mode needs a built app (`pnpm build:dev` for a debug build).
+pub fn toggle(app: &AppHandle) -> Result<bool, String> {
+    if let Some(window) = app.get_webview_window(LABEL) {
+        if window.is_visible().unwrap_or(false) {
+            window
+                .hide()
+                .map_err(|e| format!("Failed to hide mini window: {}", e))?;
+            return Ok(false);
+        }
+        window
+            .show()
+            .map_err(|e| format!("Failed to show mini window: {}", e))?;
+        return Ok(true);
+    }
+
+    if tauri::is_dev() {
+        return Err("Mini mode is unavailable under tauri dev; use a built app".to_string());
+    }
+    let window = WebviewWindowBuilder::new(app, LABEL, WebviewUrl::App("mini.html".into()))
+        .title("HackerAI")
+        .inner_size(360.0, 150.0)
+        .min_inner_size(280.0, 120.0)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .skip_taskbar(true)
+        .theme(theme::preference(app).forced())
+        .build()
+        .map_err(|e| format!("Failed to open mini window: {}", e))?;
+    // The app menu is attached to every window outside macOS
+    if cfg!(not(target_os = "macos")) {
+        let _ = window.remove_menu();
+    }
+    Ok(true)
+}
+
+/// Store the latest status and forward it to the mini window if it's open.
+pub fn update(app: &AppHandle, status: MiniStatus) -> Result<(), String> {
+    if let Some(state) = app.try_state::<MiniStatusState>() {
+        *state
+            .0
+            .lock()
+            .map_err(|_| "mini status lock poisoned".to_string())? = status.clone();
+    }
+    if app.get_webview_window(LABEL).is_some() {
+        let _ = app.emit_to(LABEL, "mini-status", status);
+    }
+    Ok(())
+}
+
+pub fn current(app: &AppHandle) -> MiniStatus {
+    app.try_state::<MiniStatusState>()
+        .and_then(|state| state.0.lock().ok().map(|status| status.clone()))
+        .unwrap_or_default()
+}
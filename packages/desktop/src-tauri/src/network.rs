@@ -0,0 +1,165 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Probe interval while the backend is reachable.
+const ONLINE_INTERVAL: Duration = Duration::from_secs(30);
+/// First retry after a failed probe, so short outages recover quickly. Each
+/// further failure doubles the wait up to `MAX_RETRY_INTERVAL`.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Probed on the backend origin. A static asset is cheap to serve, unlike the
+/// server-rendered landing page at `/`.
+const PROBE_PATH: &str = "/favicon.ico";
+/// Probed when the backend probe fails, to tell "no internet" apart from
+/// "backend down". Override with `HACKERAI_CONNECTIVITY_URL`, e.g. for a
+/// network that only allows the backend and an internal host.
+const DEFAULT_CONNECTIVITY_URL: &str = "https://www.gstatic.com/generate_204";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Connectivity {
+    Online,
+    Offline,
+    BackendUnreachable,
+}
+
+impl Connectivity {
+    fn event_name(self) -> &'static str {
+        match self {
+            Self::Online => "network-online",
+            Self::Offline => "network-offline",
+            Self::BackendUnreachable => "backend-unreachable",
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStatus {
+    pub state: Connectivity,
+    pub origin: String,
+    pub error: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub checked_at: u64,
+}
+
+#[derive(Default)]
+pub struct NetworkMonitor {
+    status: Mutex<Option<NetworkStatus>>,
+}
+
+/// Origin the main window is using, or the default backend while it shows a
+/// bundled page (e.g. the offline screen). Bundled pages are served from
+/// `tauri://localhost`, or `http://tauri.localhost` on Windows.
+fn backend_origin(app: &AppHandle) -> String {
+    app.get_webview_window("main")
+        .and_then(|window| window.url().ok())
+        .filter(|url| url.scheme() == "https" || url.scheme() == "http")
+        .filter(|url| {
+            url.host_str()
+                .is_some_and(|host| !host.ends_with(".localhost"))
+        })
+        .map(|url| url.origin().ascii_serialization())
+        .unwrap_or_else(crate::get_default_origin)
+}
+
+fn connectivity_url() -> String {
+    std::env::var("HACKERAI_CONNECTIVITY_URL")
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_CONNECTIVITY_URL.to_string())
+}
+
+/// Goes through the shared client, so it uses the same proxy as the backend
+/// probe. A 5xx is what proxies answer when they can't reach the host.
+async fn internet_reachable(client: &reqwest::Client) -> bool {
+    client
+        .head(connectivity_url())
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .is_ok_and(|response| !response.status().is_server_error())
+}
+
+pub fn current(app: &AppHandle) -> Option<NetworkStatus> {
+    app.try_state::<NetworkMonitor>()
+        .and_then(|monitor| monitor.status.lock().ok().and_then(|s| s.clone()))
+}
+
+/// Probe the backend once, store the result, and emit `network-online`,
+/// `network-offline`, or `backend-unreachable` if the state changed.
+pub async fn check(app: &AppHandle) -> Result<NetworkStatus, String> {
+    let monitor = app
+        .try_state::<NetworkMonitor>()
+        .ok_or("Network monitor is unavailable")?;
+    let origin = backend_origin(app);
+    let client = crate::http::client(app)?;
+
+    let probe = client
+        .head(format!("{}{}", origin, PROBE_PATH))
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await;
+    let (state, error) = match probe {
+        Ok(response) if response.status().is_server_error() => (
+            Connectivity::BackendUnreachable,
+            Some(format!("HTTP {}", response.status())),
+        ),
+        Ok(_) => (Connectivity::Online, None),
+        Err(e) => {
+            let state = if internet_reachable(&client).await {
+                Connectivity::BackendUnreachable
+            } else {
+                Connectivity::Offline
+            };
+            (state, Some(e.to_string()))
+        }
+    };
+    let status = NetworkStatus {
+        state,
+        origin,
+        error,
+        checked_at: crate::unix_millis(SystemTime::now()),
+    };
+
+    let previous = monitor
+        .status
+        .lock()
+        .map_err(|_| "network status lock poisoned".to_string())?
+        .replace(status.clone())
+        .map(|previous| previous.state);
+    if previous != Some(state) {
+        log::info!("Connectivity changed to {:?}", state);
+        let _ = app.emit(state.event_name(), status.clone());
+    }
+    Ok(status)
+}
+
+/// Keep probing in the background for the lifetime of the app.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut retry = RETRY_INTERVAL;
+        loop {
+            let delay = match check(&app).await {
+                Ok(status) if status.state == Connectivity::Online => {
+                    retry = RETRY_INTERVAL;
+                    ONLINE_INTERVAL
+                }
+                Ok(_) => {
+                    let delay = retry;
+                    retry = (retry * 2).min(MAX_RETRY_INTERVAL);
+                    delay
+                }
+                Err(e) => {
+                    log::warn!("Connectivity check failed: {}", e);
+                    ONLINE_INTERVAL
+                }
+            };
+            tokio::time::sleep(delay).await;
+        }
+    });
+}
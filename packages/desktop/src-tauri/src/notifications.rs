@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, UserAttentionType};
+use tauri_plugin_notification::NotificationExt;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentEventKind {
+    Completed,
+    Failed,
+    ApprovalRequired,
+}
+
+impl AgentEventKind {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Completed => "Agent finished",
+            Self::Failed => "Agent run failed",
+            Self::ApprovalRequired => "Approval needed",
+        }
+    }
+
+    fn default_body(self) -> &'static str {
+        match self {
+            Self::Completed => "Your agent run is complete.",
+            Self::Failed => "Your agent run stopped with an error.",
+            Self::ApprovalRequired => "The agent is waiting for your approval.",
+        }
+    }
+}
+
+/// Chat to open when the notified window is next focused. Desktop
+/// notifications have no click callback, but clicking one activates the app,
+/// so focus is the closest signal we get.
+struct PendingTarget {
+    window_label: String,
+    chat_id: String,
+}
+
+#[derive(Default)]
+pub struct PendingNotificationTarget(Mutex<Option<PendingTarget>>);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenChatEvent {
+    chat_id: String,
+}
+
+/// Show an OS notification for an agent event unless the user is already
+/// looking at `window_label`. Returns whether a notification was shown.
+pub fn notify_agent_event(
+    app: &AppHandle,
+    window_label: &str,
+    kind: AgentEventKind,
+    chat_title: Option<String>,
+    chat_id: Option<String>,
+) -> Result<bool, String> {
+    let window = app
+        .get_webview_window(window_label)
+        .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+    let in_front = window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false);
+    if in_front {
+        return Ok(false);
+    }
+
+    let body = chat_title
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| kind.default_body().to_string());
+    app.notification()
+        .builder()
+        .title(kind.title())
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    let _ = window.request_user_attention(Some(UserAttentionType::Informational));
+
+    if let Some(chat_id) = chat_id {
+        if let Some(pending) = app.try_state::<PendingNotificationTarget>() {
+            if let Ok(mut target) = pending.0.lock() {
+                *target = Some(PendingTarget {
+                    window_label: window_label.to_string(),
+                    chat_id,
+                });
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+/// Forward a pending notification target to the webview once its window is
+/// focused, so it can route to the chat that raised the notification.
+pub fn on_window_focused(app: &AppHandle, window_label: &str) {
+    let Some(pending) = app.try_state::<PendingNotificationTarget>() else {
+        return;
+    };
+    let target = match pending.0.lock() {
+        Ok(mut target) => match target.as_ref() {
+            Some(t) if t.window_label == window_label => target.take(),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+
+    if let Some(target) = target {
+        let _ = app.emit_to(
+            target.window_label.as_str(),
+            "notification-open-chat",
+            OpenChatEvent {
+                chat_id: target.chat_id,
+            },
+        );
+    }
+}
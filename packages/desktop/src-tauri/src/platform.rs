@@ -211,8 +211,9 @@ pub async fn graceful_kill(child: &mut tokio::process::Child) {
     let _ = child.wait().await;
 }
 
+/// Signal the process group led by `pid`, or just `pid` if it doesn't lead one.
 #[cfg(unix)]
-fn terminate_process_group(pid: u32, signal: libc::c_int) {
+pub fn terminate_process_group(pid: u32, signal: libc::c_int) {
     let pid = pid as libc::pid_t;
     unsafe {
         // build_command places each command in a fresh session, making the
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Outstanding wake lock holders (id -> reason). The system is kept awake
+/// while at least one is held.
+#[derive(Default)]
+pub struct WakeLockState(Mutex<WakeLocks>);
+
+#[derive(Default)]
+struct WakeLocks {
+    holders: HashMap<String, String>,
+    inhibitor: Option<Inhibitor>,
+}
+
+/// Platform mechanism keeping the machine awake.
+///
+/// - **macOS:** `caffeinate -i -w <pid>` (exits with the app).
+/// - **Linux:** `systemd-inhibit` around `tail --pid=<pid>` (exits with the app).
+/// - **Windows:** `SetThreadExecutionState` on a dedicated thread, since the
+///   request is tied to the thread that made it.
+enum Inhibitor {
+    #[cfg(not(windows))]
+    Process(std::process::Child),
+    #[cfg(windows)]
+    Thread(std::sync::mpsc::Sender<()>),
+}
+
+impl Inhibitor {
+    #[cfg(target_os = "macos")]
+    fn start() -> Result<Self, String> {
+        std::process::Command::new("caffeinate")
+            .args(["-i", "-w", &std::process::id().to_string()])
+            .spawn()
+            .map(Self::Process)
+            .map_err(|e| format!("Failed to start caffeinate: {}", e))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn start() -> Result<Self, String> {
+        use std::os::unix::process::CommandExt;
+
+        // Own process group, so `stop` also ends the `tail` child
+        std::process::Command::new("systemd-inhibit")
+            .args([
+                "--what=idle:sleep",
+                "--who=HackerAI",
+                "--why=Agent run in progress",
+                "--mode=block",
+                "tail",
+                &format!("--pid={}", std::process::id()),
+                "-f",
+                "/dev/null",
+            ])
+            .process_group(0)
+            .spawn()
+            .map(Self::Process)
+            .map_err(|e| format!("Failed to start systemd-inhibit: {}", e))
+    }
+
+    #[cfg(windows)]
+    fn start() -> Result<Self, String> {
+        const ES_CONTINUOUS: u32 = 0x8000_0000;
+        const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn SetThreadExecutionState(es_flags: u32) -> u32;
+        }
+
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel::<bool>();
+        std::thread::Builder::new()
+            .name("wake-lock".into())
+            .spawn(move || {
+                let ok =
+                    unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) } != 0;
+                let _ = ready_tx.send(ok);
+                if ok {
+                    // Blocks until released or the sender is dropped
+                    let _ = release_rx.recv();
+                    unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+                }
+            })
+            .map_err(|e| format!("Failed to start wake lock thread: {}", e))?;
+
+        match ready_rx.recv() {
+            Ok(true) => Ok(Self::Thread(release_tx)),
+            _ => Err("SetThreadExecutionState failed".to_string()),
+        }
+    }
+
+    fn stop(self) {
+        match self {
+            #[cfg(not(windows))]
+            Self::Process(mut child) => {
+                crate::platform::terminate_process_group(child.id(), libc::SIGKILL);
+                let _ = child.wait();
+            }
+            #[cfg(windows)]
+            Self::Thread(release) => {
+                let _ = release.send(());
+            }
+        }
+    }
+}
+
+/// Register a holder and start inhibiting sleep if it's the first one.
+/// Returns the id to pass to `release`.
+pub fn acquire(state: &WakeLockState, reason: String) -> Result<String, String> {
+    let mut locks = state
+        .0
+        .lock()
+        .map_err(|_| "wake lock state poisoned".to_string())?;
+    if locks.inhibitor.is_none() {
+        locks.inhibitor = Some(Inhibitor::start()?);
+        log::info!("Preventing system sleep ({})", reason);
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    locks.holders.insert(id.clone(), reason);
+    Ok(id)
+}
+
+/// Drop a holder, allowing sleep again once none are left. Returns whether
+/// `id` was held.
+pub fn release(state: &WakeLockState, id: &str) -> Result<bool, String> {
+    let mut locks = state
+        .0
+        .lock()
+        .map_err(|_| "wake lock state poisoned".to_string())?;
+    let released = locks.holders.remove(id).is_some();
+    if locks.holders.is_empty() {
+        if let Some(inhibitor) = locks.inhibitor.take() {
+            inhibitor.stop();
+            log::info!("Allowing system sleep");
+        }
+    }
+    Ok(released)
+}
+
+/// Drop every holder, e.g. on exit.
+pub fn release_all(state: &WakeLockState) {
+    if let Ok(mut locks) = state.0.lock() {
+        locks.holders.clear();
+        if let Some(inhibitor) = locks.inhibitor.take() {
+            inhibitor.stop();
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use serde::Serialize;
+#[cfg(target_os = "macos")]
+use std::path::PathBuf;
+use tauri::WebviewWindow;
+#[cfg(target_os = "macos")]
+use tauri_plugin_dialog::DialogExt;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintResult {
+    /// Where the PDF was written, or `None` if the user cancelled or the
+    /// platform print dialog was used instead.
+    pub path: Option<String>,
+    /// Whether the system print dialog was shown instead of writing a PDF.
+    pub used_print_dialog: bool,
+}
+
+/// Suggested file name from the window title, e.g. "Nmap scan of 10.0.0.1.pdf".
+#[cfg(target_os = "macos")]
+fn default_file_name(window: &WebviewWindow) -> String {
+    let title = window.title().unwrap_or_default();
+    let name: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let name = name.trim();
+    if name.is_empty() {
+        "HackerAI Chat.pdf".to_string()
+    } else {
+        format!("{}.pdf", name)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn pick_save_path(window: &WebviewWindow, file_name: Option<String>) -> Option<PathBuf> {
+    window
+        .dialog()
+        .file()
+        .set_parent(window)
+        .set_title("Export Chat as PDF")
+        .set_file_name(file_name.unwrap_or_else(|| default_file_name(window)))
+        .add_filter("PDF", &["pdf"])
+        .blocking_save_file()
+        .and_then(|path| path.into_path().ok())
+}
+
+/// Whether `WKWebView.createPDF` is available. It was added in macOS 11, and
+/// the app still supports 10.15.
+#[cfg(target_os = "macos")]
+fn can_render_pdf() -> bool {
+    use objc2::ClassType;
+    use objc2_web_kit::WKWebView;
+
+    WKWebView::class().responds_to(objc2::sel!(createPDFWithConfiguration:completionHandler:))
+}
+
+/// Render the window's current page to PDF bytes with
+/// `WKWebView.createPDF` (macOS 11+).
+#[cfg(target_os = "macos")]
+async fn render_pdf(window: &WebviewWindow) -> Result<Vec<u8>, String> {
+    use block2::RcBlock;
+    use objc2_foundation::{NSData, NSError};
+    use objc2_web_kit::WKWebView;
+    use std::sync::Mutex;
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<Vec<u8>, String>>();
+    let tx = Mutex::new(Some(tx));
+    window
+        .with_webview(move |webview| {
+            let handler = RcBlock::new(move |data: *mut NSData, error: *mut NSError| {
+                // SAFETY: WebKit passes either valid NSData or a valid NSError
+                let result = match unsafe { data.as_ref() } {
+                    Some(data) => Ok(data.bytes().to_vec()),
+                    None => Err(unsafe { error.as_ref() }
+                        .map(|e| e.localizedDescription().to_string())
+                        .unwrap_or_else(|| "unknown error".to_string())),
+                };
+                if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                    let _ = tx.send(result);
+                }
+            });
+            // SAFETY: on macOS the platform webview is a WKWebView, and this
+            // closure runs on the main thread
+            unsafe {
+                let wk_webview: &WKWebView = &*webview.inner().cast();
+                wk_webview.createPDFWithConfiguration_completionHandler(None, &handler);
+            }
+        })
+        .map_err(|e| format!("Failed to access webview: {}", e))?;
+
+    rx.await
+        .map_err(|_| "PDF rendering was cancelled".to_string())?
+        .map_err(|e| format!("Failed to render PDF: {}", e))
+}
+
+fn open_print_dialog(window: &WebviewWindow) -> Result<PrintResult, String> {
+    window
+        .print()
+        .map_err(|e| format!("Failed to open print dialog: {}", e))?;
+    Ok(PrintResult {
+        path: None,
+        used_print_dialog: true,
+    })
+}
+
+/// Save the window's current page as a PDF chosen through a save dialog.
+///
+/// - **macOS 11+:** renders the page with WebKit and writes the PDF directly.
+/// - **macOS 10.15, Windows/Linux:** opens the system print dialog, whose "Microsoft Print
+///   to PDF" / "Print to File" destinations save a PDF; the webviews there
+///   don't expose PDF rendering through Tauri.
+pub async fn print_to_pdf(
+    window: &WebviewWindow,
+    file_name: Option<String>,
+) -> Result<PrintResult, String> {
+    #[cfg(target_os = "macos")]
+    {
+        if !can_render_pdf() {
+            return open_print_dialog(window);
+        }
+        let dialog_window = window.clone();
+        let path =
+            tauri::async_runtime::spawn_blocking(move || pick_save_path(&dialog_window, file_name))
+                .await
+                .map_err(|e| format!("Save dialog failed: {}", e))?;
+        let Some(path) = path else {
+            return Ok(PrintResult {
+                path: None,
+                used_print_dialog: false,
+            });
+        };
+
+        let pdf = render_pdf(window).await?;
+        tokio::fs::write(&path, pdf)
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        log::info!("Exported chat to PDF");
+        Ok(PrintResult {
+            path: Some(path.display().to_string()),
+            used_print_dialog: false,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = file_name;
+        open_print_dialog(window)
+    }
+}
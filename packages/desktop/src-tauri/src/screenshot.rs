@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+
+use crate::{downloads, settings};
+
+/// Time for hidden windows to disappear before the capture starts.
+const HIDE_DELAY: Duration = Duration::from_millis(300);
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureMode {
+    /// Entire screen (all displays where the tool supports it).
+    Screen,
+    /// A window picked interactively, or the focused one where the platform
+    /// can't ask.
+    Window,
+    /// `region` if given, otherwise a rectangle selected interactively.
+    Region,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct Region {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotResult {
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+    /// Capture time in milliseconds since the Unix epoch.
+    pub captured_at: u64,
+}
+
+/// Configured evidence directory, falling back to "HackerAI Evidence" in the
+/// user's documents folder.
+pub fn evidence_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = match settings::load(app).evidence_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .document_dir()
+            .map_err(|e| format!("Documents directory is unavailable: {}", e))?
+            .join("HackerAI Evidence"),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Mkdir error: {}", e))?;
+    Ok(dir)
+}
+
+/// Change the evidence directory; `None` restores the default.
+pub fn set_evidence_dir(app: &AppHandle, dir: Option<String>) -> Result<String, String> {
+    if let Some(ref dir) = dir {
+        let path = Path::new(dir);
+        if !path.is_absolute() {
+            return Err(format!("Evidence directory must be absolute: {}", dir));
+        }
+        std::fs::create_dir_all(path).map_err(|e| format!("Mkdir error: {}", e))?;
+    }
+    settings::update(app, |settings| settings.evidence_dir = dir)?;
+    evidence_dir(app).map(|dir| dir.display().to_string())
+}
+
+/// A screenshot tool invocation. `interactive` is set when the tool asks the
+/// user what to capture, so exiting without a file means the selection was
+/// cancelled rather than that the capture failed.
+struct CaptureCommand {
+    command: Command,
+    interactive: bool,
+}
+
+/// UTC timestamp for file names, e.g. `20240131T235959Z`.
+fn file_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn capture_command(
+    mode: CaptureMode,
+    region: Option<Region>,
+    path: &Path,
+) -> Result<CaptureCommand, String> {
+    let mut command = Command::new("screencapture");
+    command.arg("-x");
+    let interactive = match (mode, region) {
+        (CaptureMode::Screen, _) => false,
+        (CaptureMode::Window, _) => {
+            command.args(["-i", "-W"]);
+            true
+        }
+        (CaptureMode::Region, Some(r)) => {
+            command.arg(format!("-R{},{},{},{}", r.x, r.y, r.width, r.height));
+            false
+        }
+        (CaptureMode::Region, None) => {
+            command.args(["-i", "-s"]);
+            true
+        }
+    };
+    command.arg(path);
+    Ok(CaptureCommand {
+        command,
+        interactive,
+    })
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn has_tool(name: &str) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::env::var_os("PATH")
+        .map(|paths| {
+            std::env::split_paths(&paths).any(|dir| {
+                dir.join(name)
+                    .metadata()
+                    .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Use the first installed tool that supports the request: grim (Wayland),
+/// gnome-screenshot, scrot, then ImageMagick's `import`.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn capture_command(
+    mode: CaptureMode,
+    region: Option<Region>,
+    path: &Path,
+) -> Result<CaptureCommand, String> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+
+    if wayland && has_tool("grim") {
+        match (mode, region) {
+            (CaptureMode::Screen, _) => {
+                let mut command = Command::new("grim");
+                command.arg(path);
+                return Ok(CaptureCommand {
+                    command,
+                    interactive: false,
+                });
+            }
+            (CaptureMode::Region, Some(r)) => {
+                let mut command = Command::new("grim");
+                command
+                    .arg("-g")
+                    .arg(format!("{},{} {}x{}", r.x, r.y, r.width, r.height))
+                    .arg(path);
+                return Ok(CaptureCommand {
+                    command,
+                    interactive: false,
+                });
+            }
+            (CaptureMode::Region, None) if has_tool("slurp") => {
+                let mut command = Command::new("sh");
+                command
+                    .args(["-c", "grim -g \"$(slurp)\" \"$1\"", "sh"])
+                    .arg(path);
+                return Ok(CaptureCommand {
+                    command,
+                    interactive: true,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if has_tool("gnome-screenshot") {
+        // -w takes the focused window; only -a asks for a selection
+        let flag = match (mode, region) {
+            (CaptureMode::Screen, _) => Some(None),
+            (CaptureMode::Window, _) => Some(Some("-w")),
+            (CaptureMode::Region, None) => Some(Some("-a")),
+            (CaptureMode::Region, Some(_)) => None,
+        };
+        if let Some(flag) = flag {
+            let mut command = Command::new("gnome-screenshot");
+            command.args(flag).arg("-f").arg(path);
+            return Ok(CaptureCommand {
+                command,
+                interactive: flag == Some("-a"),
+            });
+        }
+    }
+
+    if !wayland && has_tool("scrot") {
+        let mut command = Command::new("scrot");
+        command.arg("--overwrite");
+        let interactive = match (mode, region) {
+            (CaptureMode::Screen, _) => false,
+            (CaptureMode::Window, _) => {
+                command.arg("--focused");
+                false
+            }
+            (CaptureMode::Region, Some(r)) => {
+                command
+                    .arg("--autoselect")
+                    .arg(format!("{},{},{},{}", r.x, r.y, r.width, r.height));
+                false
+            }
+            (CaptureMode::Region, None) => {
+                command.arg("--select");
+                true
+            }
+        };
+        command.arg(path);
+        return Ok(CaptureCommand {
+            command,
+            interactive,
+        });
+    }
+
+    if !wayland && has_tool("import") {
+        let mut command = Command::new("import");
+        let interactive = match (mode, region) {
+            (CaptureMode::Screen, _) => {
+                command.args(["-window", "root"]);
+                false
+            }
+            (CaptureMode::Region, Some(r)) => {
+                command
+                    .args(["-window", "root", "-crop"])
+                    .arg(format!("{}x{}+{}+{}", r.width, r.height, r.x, r.y));
+                false
+            }
+            // Without -window, import lets the user click a window or drag
+            // a rectangle
+            (CaptureMode::Window, _) | (CaptureMode::Region, None) => true,
+        };
+        command.arg(path);
+        return Ok(CaptureCommand {
+            command,
+            interactive,
+        });
+    }
+
+    Err(
+        "No supported screenshot tool found (install grim, gnome-screenshot, scrot, or ImageMagick)"
+            .to_string(),
+    )
+}
+
+/// PowerShell + System.Drawing. Windows has no scriptable interactive
+/// picker, so only the full virtual screen or an explicit region work.
+#[cfg(windows)]
+fn capture_command(
+    mode: CaptureMode,
+    region: Option<Region>,
+    path: &Path,
+) -> Result<CaptureCommand, String> {
+    // DPI awareness comes first: otherwise scaled displays report logical
+    // sizes and the capture is cropped
+    const SCRIPT: &str = r#"
+Add-Type -Namespace Native -Name Dpi -MemberDefinition '[DllImport("user32.dll")] public static extern bool SetProcessDPIAware();'
+[Native.Dpi]::SetProcessDPIAware() | Out-Null
+Add-Type -AssemblyName System.Windows.Forms, System.Drawing
+$x = [int]$env:HACKERAI_CAPTURE_X; $y = [int]$env:HACKERAI_CAPTURE_Y
+$w = [int]$env:HACKERAI_CAPTURE_W; $h = [int]$env:HACKERAI_CAPTURE_H
+if ($w -le 0 -or $h -le 0) {
+  $screen = [System.Windows.Forms.SystemInformation]::VirtualScreen
+  $x = $screen.X; $y = $screen.Y; $w = $screen.Width; $h = $screen.Height
+}
+$bitmap = New-Object System.Drawing.Bitmap $w, $h
+$graphics = [System.Drawing.Graphics]::FromImage($bitmap)
+$graphics.CopyFromScreen($x, $y, 0, 0, $bitmap.Size)
+$bitmap.Save($env:HACKERAI_CAPTURE_PATH, [System.Drawing.Imaging.ImageFormat]::Png)
+$graphics.Dispose(); $bitmap.Dispose()
+"#;
+
+    let region = match (mode, region) {
+        (CaptureMode::Screen, _) => None,
+        (CaptureMode::Region, Some(region)) => Some(region),
+        (CaptureMode::Window, _) | (CaptureMode::Region, None) => {
+            return Err(
+                "Interactive window and region capture isn't supported on Windows".to_string(),
+            )
+        }
+    };
+
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("HACKERAI_CAPTURE_PATH", path);
+    if let Some(r) = region {
+        command
+            .env("HACKERAI_CAPTURE_X", r.x.to_string())
+            .env("HACKERAI_CAPTURE_Y", r.y.to_string())
+            .env("HACKERAI_CAPTURE_W", r.width.to_string())
+            .env("HACKERAI_CAPTURE_H", r.height.to_string());
+    }
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    Ok(CaptureCommand {
+        command,
+        interactive: false,
+    })
+}
+
+/// Capture a screenshot into the evidence directory and return its path and
+/// SHA-256. With `hide_app`, HackerAI's windows are hidden during the capture
+/// so they don't cover what's being documented. Returns `None` when the user
+/// cancels an interactive selection.
+pub async fn capture(
+    app: &AppHandle,
+    mode: CaptureMode,
+    region: Option<Region>,
+    hide_app: bool,
+) -> Result<Option<ScreenshotResult>, String> {
+    if let Some(r) = region {
+        if r.width == 0 || r.height == 0 {
+            return Err("Capture region must not be empty".to_string());
+        }
+    }
+
+    let dir = evidence_dir(app)?;
+    let now = SystemTime::now();
+    let stem = format!("screenshot-{}", file_timestamp(now));
+    let mut path = dir.join(format!("{}.png", stem));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.png", stem, n));
+        n += 1;
+    }
+    let CaptureCommand {
+        mut command,
+        interactive,
+    } = capture_command(mode, region, &path)?;
+
+    let hidden: Vec<_> = if hide_app {
+        app.webview_windows()
+            .into_values()
+            .filter(|window| window.is_visible().unwrap_or(false))
+            .filter(|window| window.hide().is_ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if !hidden.is_empty() {
+        tokio::time::sleep(HIDE_DELAY).await;
+    }
+
+    let output = command.output().await;
+
+    for window in &hidden {
+        let _ = window.show();
+    }
+
+    let output = output.map_err(|e| format!("Failed to run screenshot tool: {}", e))?;
+    if !path.exists() {
+        // Interactive pickers exit without writing a file when cancelled
+        if interactive {
+            return Ok(None);
+        }
+        return Err(format!(
+            "Screenshot failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("Read error: {}", e))?;
+    let sha256 = downloads::to_hex(&Sha256::digest(&bytes));
+    log::info!("Saved screenshot to {}", path.display());
+
+    Ok(Some(ScreenshotResult {
+        path: path.display().to_string(),
+        sha256,
+        size: bytes.len() as u64,
+        captured_at: crate::unix_millis(now),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> String {
+        file_timestamp(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn formats_the_epoch() {
+        assert_eq!(at(0), "19700101T000000Z");
+    }
+
+    #[test]
+    fn formats_leap_days() {
+        assert_eq!(at(1_709_164_800), "20240229T000000Z");
+        assert_eq!(at(1_709_251_199), "20240229T235959Z");
+        assert_eq!(at(951_827_696), "20000229T123456Z");
+    }
+
+    #[test]
+    fn formats_the_day_after_a_century_leap_day() {
+        assert_eq!(at(951_868_800), "20000301T000000Z");
+    }
+}
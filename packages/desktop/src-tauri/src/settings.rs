@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::shortcut;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Serializes read-modify-write cycles so concurrent commands don't clobber
+/// each other's changes.
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Window appearance. `Dark` matches the theme windows had before the
+/// preference existed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    System,
+    #[default]
+    Dark,
+    Light,
+}
+
+/// Native preferences persisted in the app data directory. Unknown or missing
+/// fields fall back to their defaults so older files keep loading.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    pub zoom_level: f64,
+    /// Accelerator that toggles the main window; `None` disables it.
+    pub global_shortcut: Option<String>,
+    /// Accelerator that toggles the mini window; `None` disables it.
+    pub mini_mode_shortcut: Option<String>,
+    pub theme: ThemePreference,
+    /// Hide to the tray when launched at login.
+    pub start_minimized: bool,
+    /// Where downloads are saved; `None` uses the system downloads folder.
+    pub download_dir: Option<String>,
+    /// Where screenshots are saved; `None` uses "HackerAI Evidence" in the
+    /// documents folder.
+    pub evidence_dir: Option<String>,
+    /// Origin of a self-hosted instance; `None` uses https://hackerai.co.
+    pub server_origin: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            zoom_level: 1.0,
+            global_shortcut: Some(shortcut::DEFAULT_SHORTCUT.to_string()),
+            mini_mode_shortcut: Some(shortcut::DEFAULT_MINI_MODE_SHORTCUT.to_string()),
+            theme: ThemePreference::default(),
+            start_minimized: false,
+            download_dir: None,
+            evidence_dir: None,
+            server_origin: None,
+        }
+    }
+}
+
+fn settings_file(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SETTINGS_FILE))
+}
+
+fn read(app: &AppHandle) -> Settings {
+    let Some(path) = settings_file(app) else {
+        return Settings::default();
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable settings file: {}", e);
+            Settings::default()
+        }),
+        Err(_) => Settings::default(),
+    }
+}
+
+fn write(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_file(app).ok_or("App data directory is unavailable")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Mkdir error: {}", e))?;
+    }
+    let json =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Load settings, using defaults when the file is missing or invalid.
+pub fn load(app: &AppHandle) -> Settings {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read(app)
+}
+
+/// Apply `change` to the stored settings and persist the result.
+pub fn update(app: &AppHandle, change: impl FnOnce(&mut Settings)) -> Result<Settings, String> {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = read(app);
+    change(&mut settings);
+    write(app, &settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,133 @@
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::{mini_window, settings, tray};
+
+pub const DEFAULT_SHORTCUT: &str = "CmdOrCtrl+Shift+H";
+/// Avoids ⌥⌘H, the macOS "Hide Others" shortcut.
+pub const DEFAULT_MINI_MODE_SHORTCUT: &str = "CmdOrCtrl+Alt+Shift+H";
+
+/// Plugin with a handler that toggles the main window (or the mini window,
+/// for the mini mode shortcut) on key press.
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            if is_mini_mode_shortcut(app, shortcut) {
+                if let Err(e) = mini_window::toggle(app) {
+                    log::warn!("{}", e);
+                }
+            } else {
+                toggle_main_window(app);
+            }
+        })
+        .build()
+}
+
+fn is_mini_mode_shortcut(app: &AppHandle, shortcut: &Shortcut) -> bool {
+    settings::load(app)
+        .mini_mode_shortcut
+        .and_then(|mini| mini.parse::<Shortcut>().ok())
+        .is_some_and(|mini| &mini == shortcut)
+}
+
+/// Register the persisted shortcuts, if any, on startup.
+pub fn init(app: &AppHandle) {
+    let settings = settings::load(app);
+    for shortcut in [settings.global_shortcut, settings.mini_mode_shortcut]
+        .into_iter()
+        .flatten()
+    {
+        if let Err(e) = app.global_shortcut().register(shortcut.as_str()) {
+            log::warn!("Failed to register global shortcut '{}': {}", shortcut, e);
+        }
+    }
+}
+
+/// Swap the registration of `previous` for `shortcut`. The previous shortcut
+/// is restored if the new one can't be registered, e.g. because another app
+/// already owns it. `other` is the app's other shortcut, which `shortcut`
+/// must not collide with.
+fn replace(
+    app: &AppHandle,
+    previous: Option<&str>,
+    shortcut: Option<&str>,
+    other: Option<&str>,
+) -> Result<(), String> {
+    if let Some(accelerator) = shortcut {
+        let parsed = accelerator
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))?;
+        if other.and_then(|other| other.parse::<Shortcut>().ok()) == Some(parsed) {
+            return Err(format!("'{}' is already in use", accelerator));
+        }
+    }
+
+    let manager = app.global_shortcut();
+    if let Some(previous) = previous {
+        if manager.is_registered(previous) {
+            manager
+                .unregister(previous)
+                .map_err(|e| format!("Failed to unregister shortcut: {}", e))?;
+        }
+    }
+
+    if let Some(accelerator) = shortcut {
+        if let Err(e) = manager.register(accelerator) {
+            if let Some(previous) = previous {
+                let _ = manager.register(previous);
+            }
+            return Err(format!("Failed to register '{}': {}", accelerator, e));
+        }
+    }
+    Ok(())
+}
+
+/// Replace the global shortcut (`None` disables it) and persist the choice.
+pub fn set(app: &AppHandle, shortcut: Option<String>) -> Result<Option<String>, String> {
+    let current = settings::load(app);
+    replace(
+        app,
+        current.global_shortcut.as_deref(),
+        shortcut.as_deref(),
+        current.mini_mode_shortcut.as_deref(),
+    )?;
+    settings::update(app, |settings| settings.global_shortcut = shortcut.clone())?;
+    Ok(shortcut)
+}
+
+/// Replace the mini mode shortcut (`None` disables it) and persist the
+/// choice.
+pub fn set_mini_mode(app: &AppHandle, shortcut: Option<String>) -> Result<Option<String>, String> {
+    let current = settings::load(app);
+    replace(
+        app,
+        current.mini_mode_shortcut.as_deref(),
+        shortcut.as_deref(),
+        current.global_shortcut.as_deref(),
+    )?;
+    settings::update(app, |settings| {
+        settings.mini_mode_shortcut = shortcut.clone()
+    })?;
+    Ok(shortcut)
+}
+
+/// Hide the main window if it's in front, otherwise bring it forward and ask
+/// the webview to focus the chat input.
+fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    let visible = window.is_visible().unwrap_or(false);
+    let focused = window.is_focused().unwrap_or(false);
+    if visible && focused {
+        let _ = window.hide();
+        return;
+    }
+
+    tray::show_main_window(app);
+    let _ = app.emit_to("main", "focus-chat-input", ());
+}
@@ -0,0 +1,167 @@
+use serde::Serialize;
+use std::process::Command;
+use tauri::{AppHandle, Emitter, Manager, Theme};
+
+use crate::settings::{self, ThemePreference};
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeInfo {
+    pub preference: ThemePreference,
+    /// Theme the windows are currently rendered with.
+    pub theme: Theme,
+    /// OS dark/light preference, if it could be read.
+    pub system: Option<Theme>,
+}
+
+impl ThemePreference {
+    /// Theme to force on windows, or `None` to follow the OS.
+    pub fn forced(self) -> Option<Theme> {
+        match self {
+            Self::System => None,
+            Self::Dark => Some(Theme::Dark),
+            Self::Light => Some(Theme::Light),
+        }
+    }
+}
+
+/// Read the OS dark/light preference directly. Windows only report it while
+/// following the system, so this also works while an override is active.
+#[cfg(target_os = "macos")]
+fn system_theme() -> Option<Theme> {
+    // The key only exists in dark mode; reading it fails in light mode
+    let output = Command::new("defaults")
+        .args(["read", "-g", "AppleInterfaceStyle"])
+        .output()
+        .ok()?;
+    let dark = output.status.success()
+        && String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .eq_ignore_ascii_case("dark");
+    Some(if dark { Theme::Dark } else { Theme::Light })
+}
+
+#[cfg(target_os = "windows")]
+fn system_theme() -> Option<Theme> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    // e.g. "    AppsUseLightTheme    REG_DWORD    0x0"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = stdout
+        .lines()
+        .find(|line| line.contains("AppsUseLightTheme"))?
+        .split_whitespace()
+        .last()?;
+    match value {
+        "0x0" => Some(Theme::Dark),
+        _ => Some(Theme::Light),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn system_theme() -> Option<Theme> {
+    let gsettings = |key: &str| {
+        Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.interface", key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .trim()
+                    .to_lowercase()
+            })
+    };
+    // 'prefer-dark' / 'prefer-light' on GNOME 42+; 'default' defers to the
+    // GTK theme name, which is how older desktops express dark mode
+    match gsettings("color-scheme").as_deref() {
+        Some("'prefer-dark'") => return Some(Theme::Dark),
+        Some("'prefer-light'") => return Some(Theme::Light),
+        _ => {}
+    }
+    let gtk_theme = gsettings("gtk-theme")?;
+    Some(if gtk_theme.contains("dark") {
+        Theme::Dark
+    } else {
+        Theme::Light
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn system_theme() -> Option<Theme> {
+    None
+}
+
+/// Apply a preference to every open webview window.
+fn apply(app: &AppHandle, preference: ThemePreference) {
+    for (label, window) in app.webview_windows() {
+        if let Err(e) = window.set_theme(preference.forced()) {
+            log::warn!("Failed to set theme for '{}': {}", label, e);
+        }
+    }
+}
+
+pub fn preference(app: &AppHandle) -> ThemePreference {
+    settings::load(app).theme
+}
+
+pub fn info(app: &AppHandle) -> ThemeInfo {
+    let preference = preference(app);
+    let system = system_theme();
+    let theme = preference
+        .forced()
+        .or(system)
+        .or_else(|| {
+            app.get_webview_window("main")
+                .and_then(|window| window.theme().ok())
+        })
+        .unwrap_or(Theme::Dark);
+    ThemeInfo {
+        preference,
+        theme,
+        system,
+    }
+}
+
+/// Set, apply, and persist the theme preference, notifying every window.
+pub fn set(app: &AppHandle, preference: ThemePreference) -> Result<ThemeInfo, String> {
+    settings::update(app, |settings| settings.theme = preference)?;
+    apply(app, preference);
+    let info = info(app);
+    let _ = app.emit("theme-changed", info.clone());
+    Ok(info)
+}
+
+/// Re-apply the persisted preference, e.g. on startup.
+pub fn restore(app: &AppHandle) {
+    apply(app, preference(app));
+}
+
+/// Handle `WindowEvent::ThemeChanged`. Only fires for OS appearance changes
+/// while following the system, or when `set` switches the theme.
+pub fn on_theme_changed(app: &AppHandle, theme: Theme) {
+    let preference = preference(app);
+    if preference != ThemePreference::System {
+        return;
+    }
+    let _ = app.emit(
+        "theme-changed",
+        ThemeInfo {
+            preference,
+            theme,
+            system: Some(theme),
+        },
+    );
+}
@@ -0,0 +1,76 @@
+use tauri::menu::{MenuBuilder, MenuEvent, MenuItemBuilder};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+
+pub const TRAY_ID: &str = "main";
+
+const OPEN: &str = "tray_open";
+const MINI_MODE: &str = "tray_mini_mode";
+const CHECK_UPDATES: &str = "tray_check_updates";
+const QUIT: &str = "tray_quit";
+
+/// Create the tray / menu-bar icon. While it exists, closing the main window
+/// hides it instead of quitting (see `is_active`).
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let menu = MenuBuilder::new(app)
+        .item(&MenuItemBuilder::with_id(OPEN, "Open HackerAI").build(app)?)
+        .item(&MenuItemBuilder::with_id(MINI_MODE, "Mini Mode").build(app)?)
+        .item(&MenuItemBuilder::with_id(CHECK_UPDATES, "Check for Updates…").build(app)?)
+        .separator()
+        .item(&MenuItemBuilder::with_id(QUIT, "Quit HackerAI").build(app)?)
+        .build()?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("HackerAI")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            // Not emitted on Linux, where clicking always opens the menu
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Whether the tray icon was created, i.e. the app stays reachable with no
+/// visible windows.
+pub fn is_active(app: &AppHandle) -> bool {
+    app.tray_by_id(TRAY_ID).is_some()
+}
+
+/// Show, unminimize, and focus the main window.
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        OPEN => show_main_window(app),
+        MINI_MODE => {
+            if let Err(e) = crate::mini_window::toggle(app) {
+                log::warn!("{}", e);
+            }
+        }
+        CHECK_UPDATES => {
+            let handle = app.clone();
+            tauri::async_runtime::spawn(crate::run_update_check(handle, false));
+        }
+        QUIT => app.exit(0),
+        _ => {}
+    }
+}
@@ -0,0 +1,50 @@
+use tauri::{AppHandle, Manager};
+
+use crate::settings;
+
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+const ZOOM_STEP: f64 = 0.1;
+
+fn clamp(level: f64) -> f64 {
+    if !level.is_finite() {
+        return 1.0;
+    }
+    // Round to the step size so repeated zooming doesn't drift (1.2000000001)
+    ((level / ZOOM_STEP).round() * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM)
+}
+
+/// Apply a zoom factor to every open webview window.
+pub fn apply(app: &AppHandle, level: f64) {
+    for (label, window) in app.webview_windows() {
+        if let Err(e) = window.set_zoom(level) {
+            log::warn!("Failed to set zoom for '{}': {}", label, e);
+        }
+    }
+}
+
+/// Set, apply, and persist the zoom level. Returns the clamped level.
+pub fn set(app: &AppHandle, level: f64) -> Result<f64, String> {
+    let level = clamp(level);
+    apply(app, level);
+    settings::update(app, |settings| settings.zoom_level = level)?;
+    Ok(level)
+}
+
+/// Adjust the current zoom level by `steps` increments (negative zooms out).
+pub fn step(app: &AppHandle, steps: i32) -> Result<f64, String> {
+    let current = clamp(settings::load(app).zoom_level);
+    set(app, current + f64::from(steps) * ZOOM_STEP)
+}
+
+/// Re-apply the persisted zoom level, e.g. on startup.
+pub fn restore(app: &AppHandle) {
+    let level = clamp(settings::load(app).zoom_level);
+    if level != 1.0 {
+        apply(app, level);
+    }
+}
+
+pub fn current(app: &AppHandle) -> f64 {
+    clamp(settings::load(app).zoom_level)
+}
@@ -12,6 +12,7 @@ import {
 const AUTHKIT_BYPASS_PATHS = new Set([
   "/api/health/core",
   "/api/health/trigger-agent-mode",
+  "/api/desktop/crash-reports",
   "/robots.txt",
   "/sitemap.xml",
 ]);